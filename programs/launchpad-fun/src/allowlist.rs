@@ -0,0 +1,133 @@
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, InitSpace, Pubkey};
+use anchor_lang::solana_program::hash::hashv;
+
+/// Domain separators so an inner node can never be replayed as a leaf.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Allowlist presale phase set at `create_token`.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct Allowlist {
+    /// Root of the Merkle tree of `(buyer, allocation)` leaves
+    pub merkle_root: [u8; 32],
+    /// Unix timestamp at which the presale ends and public trading begins
+    pub ends_at: i64,
+}
+
+/// Proof that a buyer is on the allowlist with a given allocation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct AllowlistProof {
+    /// Tokens the buyer may buy during the presale, in base units
+    pub allocation: u64,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Hashes a buyer and their allocation into an allowlist leaf.
+pub fn allowlist_leaf(buyer: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, buyer.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+/// Hashes two nodes in sorted order so proofs don't need to carry left/right flags.
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Pairs up a level of the tree, an odd node at the end is promoted as is.
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_pair(left, right),
+            [node] => *node,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Folds `proof` into `leaf` and compares the result with `root`.
+pub fn verify_allowlist_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let node = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    node == *root
+}
+
+/// Builds the Merkle root over `leaves`, for off-chain allowlist generation.
+pub fn allowlist_merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Builds the proof for the leaf at `index`, for off-chain allowlist generation.
+pub fn allowlist_merkle_proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+        level = next_level(&level);
+    }
+    proof
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn allowlist(size: u64) -> (Vec<Pubkey>, Vec<[u8; 32]>) {
+        let buyers: Vec<Pubkey> = (0..size).map(|_| Pubkey::new_unique()).collect();
+        let leaves = buyers
+            .iter()
+            .enumerate()
+            .map(|(i, buyer)| allowlist_leaf(buyer, 1_000 * (i as u64 + 1)))
+            .collect();
+        (buyers, leaves)
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_leaf() {
+        for size in [1, 2, 3, 5, 8, 13] {
+            let (_, leaves) = allowlist(size);
+            let root = allowlist_merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = allowlist_merkle_proof(&leaves, index);
+                assert!(verify_allowlist_proof(&root, *leaf, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_buyer_or_allocation() {
+        let (buyers, leaves) = allowlist(5);
+        let root = allowlist_merkle_root(&leaves);
+        let proof = allowlist_merkle_proof(&leaves, 2);
+
+        assert!(verify_allowlist_proof(
+            &root,
+            allowlist_leaf(&buyers[2], 3_000),
+            &proof
+        ));
+        assert!(!verify_allowlist_proof(
+            &root,
+            allowlist_leaf(&buyers[2], 3_001),
+            &proof
+        ));
+        assert!(!verify_allowlist_proof(
+            &root,
+            allowlist_leaf(&Pubkey::new_unique(), 3_000),
+            &proof
+        ));
+    }
+}
@@ -70,4 +70,184 @@ pub enum LaunchPadErrorCode {
 
     #[msg("Insufficient asset liquidity")]
     InsufficientAssetLiquidity,
+
+    #[msg("No pending authority")]
+    NoPendingAuthority,
+
+    #[msg("Invalid pending authority")]
+    InvalidPendingAuthority,
+
+    #[msg("Invalid treasury")]
+    InvalidTreasury,
+
+    #[msg("Insufficient protocol fees")]
+    InsufficientProtocolFees,
+
+    #[msg("Slippage exceeded")]
+    SlippageExceeded,
+
+    #[msg("Invalid curve parameters")]
+    InvalidCurveParams,
+
+    #[msg("Curve type not allowed")]
+    CurveTypeNotAllowed,
+
+    #[msg("Invalid allowed curves")]
+    InvalidAllowedCurves,
+
+    #[msg("Invalid token supply bounds")]
+    InvalidTokenSupplyBounds,
+
+    #[msg("Invalid token supply")]
+    InvalidTokenSupply,
+
+    #[msg("Invalid token decimals")]
+    InvalidTokenDecimals,
+
+    #[msg("Invalid graduation allocation")]
+    InvalidGraduationAllocation,
+
+    #[msg("Invalid quote mint")]
+    InvalidQuoteMint,
+
+    #[msg("Quote mint not allowed")]
+    QuoteMintNotAllowed,
+
+    #[msg("Quote mint already allowed")]
+    QuoteMintAlreadyAllowed,
+
+    #[msg("Too many quote mints")]
+    TooManyQuoteMints,
+
+    #[msg("Missing quote accounts")]
+    MissingQuoteAccounts,
+
+    #[msg("Creator fee exceeds maximum")]
+    CreatorFeeExceedsMaximum,
+
+    #[msg("No creator fees to claim")]
+    NoCreatorFeesToClaim,
+
+    #[msg("Referral fee exceeds maximum")]
+    ReferralFeeExceedsMaximum,
+
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+
+    #[msg("Traders cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("No referral fees to withdraw")]
+    NoReferralFeesToWithdraw,
+
+    #[msg("Invalid launch fee schedule")]
+    InvalidLaunchFeeSchedule,
+
+    #[msg("Invalid anti-snipe limit")]
+    InvalidAntiSnipeLimit,
+
+    #[msg("Max buy per wallet exceeded during the anti-snipe window")]
+    MaxWalletBuyExceeded,
+
+    #[msg("Initial buy requires a native SOL quote")]
+    InitialBuyRequiresNativeQuote,
+
+    #[msg("Missing initial buy accounts")]
+    MissingInitialBuyAccounts,
+
+    #[msg("Trading start time must be in the future")]
+    InvalidTradingStartTime,
+
+    #[msg("Trading has not started yet for this launch pad token")]
+    TradingNotStarted,
+
+    #[msg("Invalid allowlist")]
+    InvalidAllowlist,
+
+    #[msg("Allowlist proof required during the presale")]
+    AllowlistProofRequired,
+
+    #[msg("Invalid allowlist proof")]
+    InvalidAllowlistProof,
+
+    #[msg("Allowlist allocation exceeded")]
+    AllowlistAllocationExceeded,
+
+    #[msg("Invalid auction schedule")]
+    InvalidAuctionSchedule,
+
+    #[msg("Auction launches require a native SOL quote")]
+    AuctionRequiresNativeQuote,
+
+    #[msg("Missing auction accounts")]
+    MissingAuctionAccounts,
+
+    #[msg("Auction is not in its commit phase")]
+    AuctionNotInCommitPhase,
+
+    #[msg("Auction is not in its reveal phase")]
+    AuctionNotInRevealPhase,
+
+    #[msg("Auction deposit must be greater than zero")]
+    InvalidAuctionDeposit,
+
+    #[msg("Revealed bid does not match the commitment")]
+    InvalidAuctionReveal,
+
+    #[msg("Auction bid already revealed")]
+    AuctionBidAlreadyRevealed,
+
+    #[msg("Auction reveal phase has not ended")]
+    AuctionNotEnded,
+
+    #[msg("Auction already settled")]
+    AuctionAlreadySettled,
+
+    #[msg("Auction not settled")]
+    AuctionNotSettled,
+
+    #[msg("Vesting share exceeds the maximum")]
+    VestingShareExceedsMaximum,
+
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+
+    #[msg("Missing vesting accounts")]
+    MissingVestingAccounts,
+
+    #[msg("Vesting starts once the token graduates")]
+    VestingNotStarted,
+
+    #[msg("No vested tokens to claim")]
+    NoVestedTokensToClaim,
+
+    #[msg("Token metadata update has no fields to change")]
+    EmptyTokenMetadataUpdate,
+
+    #[msg("Invalid metadata rules")]
+    InvalidMetadataRules,
+
+    #[msg("Metadata key not allowed")]
+    MetadataKeyNotAllowed,
+
+    #[msg("Duplicate metadata key")]
+    DuplicateMetadataKey,
+
+    #[msg("Invalid metadata value length")]
+    InvalidMetadataValueLength,
+
+    #[msg("Additional metadata is only supported on Token-2022 launches")]
+    AdditionalMetadataNotSupported,
+
+    #[msg("Metaplex metadata accounts are required for SPL Token launches")]
+    MissingMetaplexAccounts,
+
+    #[msg("Account already migrated")]
+    AccountAlreadyMigrated,
+
+    #[msg("Initial buy is only allowed on launches that open trading immediately")]
+    InitialBuyNotAllowed,
+
+    #[msg("Auction launches cannot use an allowlist or the anti-snipe limit")]
+    AuctionNotAllowed,
 }
@@ -1,20 +1,84 @@
 use anchor_lang::prelude::*;
 
-use crate::{LaunchPadTokenStatus, ProtocolStatus};
+use crate::{
+    AntiSnipeLimit, CurveType, LaunchFeeSchedule, LaunchPadTokenStatus, MetadataRules,
+    ProtocolStatus, TokenSupplyBounds,
+};
 
 #[event]
 #[derive(Debug)]
 pub struct LaunchPadConfigInitialized {
     pub authority: Pubkey,
+    pub treasury: Pubkey,
     pub asset_rate: u64,
     pub creator_sell_delay: u64,
     pub graduate_threshold: u64,
     pub protocol_buy_fee: u32,
     pub protocol_sell_fee: u32,
+    pub allowed_curves: u8,
+    pub supply_bounds: TokenSupplyBounds,
     pub status: ProtocolStatus,
     pub timestamp: i64,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadConfigUpdated {
+    pub old_asset_rate: u64,
+    pub new_asset_rate: u64,
+    pub old_creator_sell_delay: u64,
+    pub new_creator_sell_delay: u64,
+    pub old_graduate_threshold: u64,
+    pub new_graduate_threshold: u64,
+    pub old_protocol_buy_fee: u32,
+    pub new_protocol_buy_fee: u32,
+    pub old_protocol_sell_fee: u32,
+    pub new_protocol_sell_fee: u32,
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub old_allowed_curves: u8,
+    pub new_allowed_curves: u8,
+    pub old_supply_bounds: TokenSupplyBounds,
+    pub new_supply_bounds: TokenSupplyBounds,
+    pub old_creator_fee_bps: u32,
+    pub new_creator_fee_bps: u32,
+    pub old_referral_fee_bps: u32,
+    pub new_referral_fee_bps: u32,
+    pub old_launch_fee: LaunchFeeSchedule,
+    pub new_launch_fee: LaunchFeeSchedule,
+    pub old_anti_snipe: AntiSnipeLimit,
+    pub new_anti_snipe: AntiSnipeLimit,
+    pub old_max_vesting_share: u32,
+    pub new_max_vesting_share: u32,
+    pub old_metadata_rules: MetadataRules,
+    pub new_metadata_rules: MetadataRules,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadAuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadAuthorityAccepted {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadAuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 #[derive(Debug)]
 pub struct LaunchPadPaused {
@@ -27,15 +91,140 @@ pub struct LaunchPadUnpaused {
     pub timestamp: i64,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct ProtocolFeesWithdrawn {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ProtocolQuoteFeesWithdrawn {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadQuoteMintAdded {
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadQuoteMintRemoved {
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 #[derive(Debug)]
 pub struct LaunchPadTokenCreated {
     pub creator: Pubkey,
     pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub curve_type: CurveType,
+    pub token_supply: u64,
+    pub decimals: u8,
+    pub graduation_token_amount: u64,
+    pub trading_starts_at: i64,
+    pub allowlist_root: [u8; 32],
+    pub allowlist_ends_at: i64,
+    pub status: LaunchPadTokenStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadTokenBought {
+    pub trader: Pubkey,
+    pub mint: Pubkey,
+    pub asset_amount: u64,
+    pub token_amount: u64,
+    pub fee: u64,
+    pub virtual_token_amount: u64,
+    pub virtual_asset_amount: u64,
+    pub virtual_graduation_amount: u64,
     pub status: LaunchPadTokenStatus,
     pub timestamp: i64,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct LaunchPadTokenSold {
+    pub trader: Pubkey,
+    pub mint: Pubkey,
+    pub asset_amount: u64,
+    pub token_amount: u64,
+    pub fee: u64,
+    pub virtual_token_amount: u64,
+    pub virtual_asset_amount: u64,
+    pub virtual_graduation_amount: u64,
+    pub status: LaunchPadTokenStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct CreatorFeesAccrued {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unclaimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct CreatorFeesClaimed {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ReferralRegistered {
+    pub referrer: Pubkey,
+    pub referral: Pubkey,
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ReferralFeesAccrued {
+    pub referrer: Pubkey,
+    pub trader: Pubkey,
+    pub mint: Pubkey,
+    pub volume: u64,
+    pub amount: u64,
+    pub lifetime_volume: u64,
+    pub unclaimed_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ReferralFeesWithdrawn {
+    pub referrer: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount: u64,
+    pub lifetime_fees: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 #[derive(Debug)]
 pub struct LaunchPadTokenGraduated {
@@ -48,3 +237,77 @@ pub struct LaunchPadTokenGraduated {
     pub status: LaunchPadTokenStatus,
     pub timestamp: i64,
 }
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionBidCommitted {
+    pub bidder: Pubkey,
+    pub mint: Pubkey,
+    pub deposit: u64,
+    pub total_deposits: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionBidRevealed {
+    pub bidder: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_revealed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionSettled {
+    pub mint: Pubkey,
+    pub total_revealed: u64,
+    pub asset_amount: u64,
+    pub token_amount: u64,
+    pub fee: u64,
+    pub status: LaunchPadTokenStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuctionBidClaimed {
+    pub bidder: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VestingCreated {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VestedTokensClaimed {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct TokenMetadataUpdated {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub field: String,
+    pub value: String,
+    pub timestamp: i64,
+}
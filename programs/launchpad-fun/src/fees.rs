@@ -0,0 +1,221 @@
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, InitSpace};
+
+use crate::{exp_neg_wad, LaunchPadErrorCode, MAX_LAUNCH_FEE, WAD};
+
+/// Exponent reached at the end of an exponential window, e^-5 leaves under 1% of the extra fee.
+const EXPONENTIAL_DECAY_RATE: u128 = 5;
+
+/// How the launch fee falls back to the base buy fee over the window.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub enum FeeDecay {
+    #[default]
+    Linear,
+    Exponential,
+}
+
+/// Buy fee schedule applied right after trading opens to deter snipers. The window starts at
+/// `LaunchPadToken::trading_starts_at`: the creation time for immediate launches, the start
+/// time for scheduled ones and the settlement time for auctions.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct LaunchFeeSchedule {
+    /// Buy fee when trading opens in parts per 1_000_000 (500_000 = 50%), 0 disables the schedule
+    pub start_fee: u32,
+    /// Seconds after trading opens over which the fee decays to the base buy fee
+    pub window: u64,
+    pub decay: FeeDecay,
+}
+
+impl LaunchFeeSchedule {
+    pub fn validate(&self, base_fee: u32) -> Result<(), LaunchPadErrorCode> {
+        if self.start_fee == 0 {
+            return Ok(());
+        }
+        if self.start_fee < base_fee || self.start_fee > MAX_LAUNCH_FEE || self.window == 0 {
+            return Err(LaunchPadErrorCode::InvalidLaunchFeeSchedule);
+        }
+        Ok(())
+    }
+
+    /// Buy fee rate `elapsed` seconds after trading opened, never below `base_fee`.
+    pub fn buy_fee_at(&self, base_fee: u32, elapsed: i64) -> Result<u32, LaunchPadErrorCode> {
+        if self.start_fee <= base_fee || elapsed >= self.window as i64 {
+            return Ok(base_fee);
+        }
+        let elapsed = elapsed.max(0) as u128;
+        let window = self.window as u128;
+        let extra_fee = (self.start_fee - base_fee) as u128;
+
+        let remaining = match self.decay {
+            FeeDecay::Linear => {
+                extra_fee
+                    .checked_mul(window - elapsed)
+                    .ok_or(LaunchPadErrorCode::MathOverflow)?
+                    / window
+            }
+            FeeDecay::Exponential => {
+                let exponent = EXPONENTIAL_DECAY_RATE
+                    .checked_mul(WAD)
+                    .and_then(|x| x.checked_mul(elapsed))
+                    .ok_or(LaunchPadErrorCode::MathOverflow)?
+                    / window;
+                extra_fee
+                    .checked_mul(exp_neg_wad(exponent)?)
+                    .ok_or(LaunchPadErrorCode::MathOverflow)?
+                    / WAD
+            }
+        };
+        Ok(base_fee + remaining as u32)
+    }
+}
+
+pub fn calculate_fee(amount: u64, fee_rate: u32) -> Result<u64, LaunchPadErrorCode> {
+    let fee = (amount as u128)
+        .checked_mul(fee_rate as u128)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?
+        .checked_div(1_000_000)
+        .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
+    Ok(fee)
+}
+
+/// Returns the smallest amount whose fee at `fee_rate` leaves at least `net_amount`.
+pub fn calculate_amount_with_fee(
+    net_amount: u64,
+    fee_rate: u32,
+) -> Result<u64, LaunchPadErrorCode> {
+    let denominator = 1_000_000u128
+        .checked_sub(fee_rate as u128)
+        .filter(|denominator| *denominator > 0)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    let amount = (net_amount as u128)
+        .checked_mul(1_000_000)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?
+        .checked_add(denominator - 1)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?
+        .checked_div(denominator)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    u64::try_from(amount).map_err(|_| LaunchPadErrorCode::MathOverflow)
+}
+
+pub fn calc_withdrawable_amount(
+    vault_balance: u64,
+    rent_exempt_minimum: u64,
+    requested_amount: Option<u64>,
+) -> Result<u64, LaunchPadErrorCode> {
+    let available = vault_balance.saturating_sub(rent_exempt_minimum);
+    let amount = requested_amount.unwrap_or(available);
+    if amount == 0 || amount > available {
+        return Err(LaunchPadErrorCode::InsufficientProtocolFees);
+    }
+    Ok(amount)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BASE_FEE: u32 = 5_000;
+
+    fn schedule(decay: FeeDecay) -> LaunchFeeSchedule {
+        LaunchFeeSchedule {
+            start_fee: 500_000,
+            window: 60,
+            decay,
+        }
+    }
+
+    #[test]
+    fn test_linear_decay() {
+        let schedule = schedule(FeeDecay::Linear);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 0).unwrap(), 500_000);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 30).unwrap(), 252_500);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 59).unwrap(), 13_250);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 60).unwrap(), BASE_FEE);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 3_600).unwrap(), BASE_FEE);
+    }
+
+    #[test]
+    fn test_exponential_decay() {
+        let schedule = schedule(FeeDecay::Exponential);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 0).unwrap(), 500_000);
+
+        let mut previous = u32::MAX;
+        for elapsed in 0..60 {
+            let fee = schedule.buy_fee_at(BASE_FEE, elapsed).unwrap();
+            assert!(fee < previous && fee > BASE_FEE);
+            previous = fee;
+        }
+        // 495_000 * e^-2.5 of extra fee halfway through the window
+        let halfway = schedule.buy_fee_at(BASE_FEE, 30).unwrap();
+        assert!(halfway.abs_diff(BASE_FEE + 40_632) <= 1);
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 60).unwrap(), BASE_FEE);
+    }
+
+    #[test]
+    fn test_disabled_schedule_charges_base_fee() {
+        let schedule = LaunchFeeSchedule::default();
+        assert!(schedule.validate(BASE_FEE).is_ok());
+        assert_eq!(schedule.buy_fee_at(BASE_FEE, 0).unwrap(), BASE_FEE);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(schedule(FeeDecay::Linear).validate(BASE_FEE).is_ok());
+        for invalid in [
+            LaunchFeeSchedule {
+                start_fee: BASE_FEE - 1,
+                ..schedule(FeeDecay::Linear)
+            },
+            LaunchFeeSchedule {
+                start_fee: MAX_LAUNCH_FEE + 1,
+                ..schedule(FeeDecay::Linear)
+            },
+            LaunchFeeSchedule {
+                window: 0,
+                ..schedule(FeeDecay::Linear)
+            },
+        ] {
+            assert!(matches!(
+                invalid.validate(BASE_FEE),
+                Err(LaunchPadErrorCode::InvalidLaunchFeeSchedule)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_calculate_amount_with_fee() {
+        for fee_rate in [BASE_FEE, 252_500, 500_000] {
+            for net_amount in [0, 1, 199, 995_000_000, 109_535_269_847] {
+                let amount = calculate_amount_with_fee(net_amount, fee_rate).unwrap();
+                assert!(amount - calculate_fee(amount, fee_rate).unwrap() >= net_amount);
+            }
+        }
+    }
+
+    #[test]
+    fn test_calc_withdrawable_amount() {
+        let rent_exempt = 890_880;
+        let vault_balance = rent_exempt + 12_000_000;
+
+        // Withdraws everything above the rent exempt minimum by default.
+        assert_eq!(
+            calc_withdrawable_amount(vault_balance, rent_exempt, None).unwrap(),
+            12_000_000
+        );
+        assert_eq!(
+            calc_withdrawable_amount(vault_balance, rent_exempt, Some(2_000_000)).unwrap(),
+            2_000_000
+        );
+        assert!(matches!(
+            calc_withdrawable_amount(vault_balance, rent_exempt, Some(12_000_001)),
+            Err(LaunchPadErrorCode::InsufficientProtocolFees)
+        ));
+        assert!(matches!(
+            calc_withdrawable_amount(rent_exempt, rent_exempt, None),
+            Err(LaunchPadErrorCode::InsufficientProtocolFees)
+        ));
+    }
+}
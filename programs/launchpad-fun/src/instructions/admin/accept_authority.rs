@@ -0,0 +1,31 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::LaunchPadConfig;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    // The proposed authority that is taking over the launch pad config.
+    pub pending_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> AcceptAuthority<'info> {
+    pub fn accept(&mut self) -> Result<()> {
+        self.launch_pad_config
+            .accept_authority(self.pending_authority.key())?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    ctx.accounts.accept()?;
+    msg!("Launch pad authority transfer accepted");
+    Ok(())
+}
@@ -0,0 +1,37 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(Accounts)]
+pub struct AddQuoteMint<'info> {
+    // The admin authority that is allowlisting the quote mint.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    // The SPL or Token-2022 mint tokens can be priced in.
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+}
+
+impl<'info> AddQuoteMint<'info> {
+    pub fn add_quote_mint(&mut self) -> Result<()> {
+        self.launch_pad_config
+            .add_quote_mint(self.quote_mint.key())?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<AddQuoteMint>) -> Result<()> {
+    ctx.accounts.add_quote_mint()?;
+    msg!("Launch pad quote mint added");
+    Ok(())
+}
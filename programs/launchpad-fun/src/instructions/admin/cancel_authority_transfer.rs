@@ -0,0 +1,31 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    // The admin authority that is withdrawing its pending proposal.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> CancelAuthorityTransfer<'info> {
+    pub fn cancel(&mut self) -> Result<()> {
+        self.launch_pad_config.cancel_authority_transfer()?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    ctx.accounts.cancel()?;
+    msg!("Launch pad authority transfer cancelled");
+    Ok(())
+}
@@ -0,0 +1,47 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{migrate_account, LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(Accounts)]
+pub struct MigrateLaunchPadConfig<'info> {
+    // The admin authority that is migrating the launch pad config, pays for the larger account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: written by an earlier layout, read by `migrate_account` once it has been grown.
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump,
+    )]
+    pub launch_pad_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateLaunchPadConfig<'info> {
+    pub fn migrate(&mut self) -> Result<()> {
+        let authority = self.authority.key();
+        migrate_account(
+            &self.launch_pad_config.to_account_info(),
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+            LaunchPadConfig::DISCRIMINATOR.len() + LaunchPadConfig::INIT_SPACE,
+            |launch_pad_config: &mut LaunchPadConfig| {
+                require_keys_eq!(
+                    launch_pad_config.authority,
+                    authority,
+                    LaunchPadErrorCode::InvalidAuthority
+                );
+                launch_pad_config.migrate()
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<MigrateLaunchPadConfig>) -> Result<()> {
+    ctx.accounts.migrate()?;
+    msg!("Launch pad config migrated");
+    Ok(())
+}
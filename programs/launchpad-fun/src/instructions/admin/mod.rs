@@ -1,3 +1,25 @@
+pub mod accept_authority;
+pub mod add_quote_mint;
+pub mod cancel_authority_transfer;
 pub mod init_launch_pad_config;
+pub mod migrate_launch_pad_config;
+pub mod pause_protocol;
+pub mod propose_authority;
+pub mod remove_quote_mint;
+pub mod unpause_protocol;
+pub mod update_launch_pad_config;
+pub mod withdraw_protocol_fees;
+pub mod withdraw_protocol_quote_fees;
 
+pub use accept_authority::*;
+pub use add_quote_mint::*;
+pub use cancel_authority_transfer::*;
 pub use init_launch_pad_config::*;
+pub use migrate_launch_pad_config::*;
+pub use pause_protocol::*;
+pub use propose_authority::*;
+pub use remove_quote_mint::*;
+pub use unpause_protocol::*;
+pub use update_launch_pad_config::*;
+pub use withdraw_protocol_fees::*;
+pub use withdraw_protocol_quote_fees::*;
@@ -0,0 +1,31 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    // The admin authority that is pausing the protocol.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> PauseProtocol<'info> {
+    pub fn pause(&mut self) -> Result<()> {
+        self.launch_pad_config.pause()?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<PauseProtocol>) -> Result<()> {
+    ctx.accounts.pause()?;
+    msg!("Launch pad protocol paused");
+    Ok(())
+}
@@ -0,0 +1,37 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ProposeAuthorityArgs {
+    pub new_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    // The admin authority that is handing over the launch pad config.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> ProposeAuthority<'info> {
+    pub fn propose(&mut self, args: ProposeAuthorityArgs) -> Result<()> {
+        self.launch_pad_config
+            .propose_authority(args.new_authority)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>, args: ProposeAuthorityArgs) -> Result<()> {
+    ctx.accounts.propose(args)?;
+    msg!("Launch pad authority transfer proposed");
+    Ok(())
+}
@@ -0,0 +1,36 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RemoveQuoteMintArgs {
+    pub quote_mint: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct RemoveQuoteMint<'info> {
+    // The admin authority that is removing the quote mint from the allowlist.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> RemoveQuoteMint<'info> {
+    pub fn remove_quote_mint(&mut self, args: RemoveQuoteMintArgs) -> Result<()> {
+        self.launch_pad_config.remove_quote_mint(args.quote_mint)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RemoveQuoteMint>, args: RemoveQuoteMintArgs) -> Result<()> {
+    ctx.accounts.remove_quote_mint(args)?;
+    msg!("Launch pad quote mint removed");
+    Ok(())
+}
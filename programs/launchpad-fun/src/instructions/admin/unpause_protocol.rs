@@ -0,0 +1,31 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(Accounts)]
+pub struct UnpauseProtocol<'info> {
+    // The admin authority that is unpausing the protocol.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> UnpauseProtocol<'info> {
+    pub fn unpause(&mut self) -> Result<()> {
+        self.launch_pad_config.unpause()?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UnpauseProtocol>) -> Result<()> {
+    ctx.accounts.unpause()?;
+    msg!("Launch pad protocol unpaused");
+    Ok(())
+}
@@ -0,0 +1,52 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{
+    AntiSnipeLimit, LaunchFeeSchedule, LaunchPadConfig, LaunchPadErrorCode, MetadataRules,
+    TokenSupplyBounds,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Default)]
+pub struct UpdateLaunchPadConfigArgs {
+    pub asset_rate: Option<u64>,
+    pub creator_sell_delay: Option<u64>,
+    pub graduate_threshold: Option<u64>,
+    pub protocol_buy_fee: Option<u32>,
+    pub protocol_sell_fee: Option<u32>,
+    pub treasury: Option<Pubkey>,
+    pub allowed_curves: Option<u8>,
+    pub supply_bounds: Option<TokenSupplyBounds>,
+    pub creator_fee_bps: Option<u32>,
+    pub referral_fee_bps: Option<u32>,
+    pub launch_fee: Option<LaunchFeeSchedule>,
+    pub anti_snipe: Option<AntiSnipeLimit>,
+    pub max_vesting_share: Option<u32>,
+    pub metadata_rules: Option<MetadataRules>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLaunchPadConfig<'info> {
+    // The admin authority that is updating the launch pad config.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+}
+
+impl<'info> UpdateLaunchPadConfig<'info> {
+    pub fn update(&mut self, args: UpdateLaunchPadConfigArgs) -> Result<()> {
+        self.launch_pad_config.update(&args)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateLaunchPadConfig>, args: UpdateLaunchPadConfigArgs) -> Result<()> {
+    ctx.accounts.update(args)?;
+    msg!("Launch pad config updated");
+    Ok(())
+}
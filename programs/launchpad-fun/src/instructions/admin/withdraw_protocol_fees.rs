@@ -0,0 +1,84 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{calc_withdrawable_amount, LaunchPadConfig, LaunchPadErrorCode, ProtocolFeesWithdrawn};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct WithdrawProtocolFeesArgs {
+    // Withdraws everything above the vault rent exempt minimum when empty
+    pub amount: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    // The admin authority that is withdrawing the protocol fees.
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+        has_one = treasury @ LaunchPadErrorCode::InvalidTreasury,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::VAULT_SEED],
+        bump = launch_pad_config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: only receives lamports, validated against launch_pad_config.treasury
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawProtocolFees<'info> {
+    pub fn withdraw(&mut self, args: WithdrawProtocolFeesArgs) -> Result<()> {
+        let vault_info = self.vault.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+        let amount = calc_withdrawable_amount(vault_info.lamports(), rent_exempt, args.amount)?;
+
+        self.transfer_fees_to_treasury(amount)?;
+
+        emit!(ProtocolFeesWithdrawn {
+            authority: self.authority.key(),
+            treasury: self.treasury.key(),
+            amount,
+            vault_balance: self.vault.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    fn transfer_fees_to_treasury(&self, amount: u64) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[
+            LaunchPadConfig::VAULT_SEED,
+            &[self.launch_pad_config.vault_bump],
+        ]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.vault.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawProtocolFees>, args: WithdrawProtocolFeesArgs) -> Result<()> {
+    ctx.accounts.withdraw(args)?;
+    msg!("Protocol fees withdrawn");
+    Ok(())
+}
@@ -0,0 +1,116 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    calc_withdrawable_amount, LaunchPadConfig, LaunchPadErrorCode, ProtocolQuoteFeesWithdrawn,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct WithdrawProtocolQuoteFeesArgs {
+    // Withdraws the whole fee balance when empty
+    pub amount: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolQuoteFees<'info> {
+    // The admin authority that is withdrawing the protocol fees.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+        has_one = authority @ LaunchPadErrorCode::InvalidAuthority,
+        has_one = treasury @ LaunchPadErrorCode::InvalidTreasury,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account(
+        seeds = [LaunchPadConfig::VAULT_SEED],
+        bump = launch_pad_config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: only owns the receiving token account, validated against launch_pad_config.treasury
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+
+    // The protocol fees collected in the quote mint
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = quote_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_quote_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawProtocolQuoteFees<'info> {
+    pub fn withdraw(&mut self, args: WithdrawProtocolQuoteFeesArgs) -> Result<()> {
+        let amount = calc_withdrawable_amount(self.vault_quote_account.amount, 0, args.amount)?;
+
+        self.transfer_fees_to_treasury(amount)?;
+        self.vault_quote_account.reload()?;
+
+        emit!(ProtocolQuoteFeesWithdrawn {
+            authority: self.authority.key(),
+            treasury: self.treasury.key(),
+            quote_mint: self.quote_mint.key(),
+            amount,
+            vault_balance: self.vault_quote_account.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    fn transfer_fees_to_treasury(&self, amount: u64) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[
+            LaunchPadConfig::VAULT_SEED,
+            &[self.launch_pad_config.vault_bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault_quote_account.to_account_info(),
+                    to: self.treasury_quote_account.to_account_info(),
+                    authority: self.vault.to_account_info(),
+                    mint: self.quote_mint.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+            self.quote_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<WithdrawProtocolQuoteFees>,
+    args: WithdrawProtocolQuoteFeesArgs,
+) -> Result<()> {
+    ctx.accounts.withdraw(args)?;
+    msg!("Protocol quote fees withdrawn");
+    Ok(())
+}
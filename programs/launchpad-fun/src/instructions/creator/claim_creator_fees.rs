@@ -0,0 +1,122 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{CreatorFeesClaimed, LaunchPadErrorCode, LaunchPadToken};
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        bump = launch_pad_token.bump,
+        has_one = creator @ LaunchPadErrorCode::InvalidCreator,
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+
+    // The accounts below are only required when the token is priced in an SPL quote mint
+    #[account(
+        address = launch_pad_token.quote_mint @ LaunchPadErrorCode::InvalidQuoteMint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = launch_pad_token,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub creator_fee_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub creator_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimCreatorFees<'info> {
+    pub fn claim(&mut self) -> Result<()> {
+        let amount = self.launch_pad_token.claim_creator_fees()?;
+
+        if self.launch_pad_token.is_native_quote() {
+            // The accrued lamports sit on top of the launch pad token's rent exemption
+            self.launch_pad_token.sub_lamports(amount)?;
+            self.creator.add_lamports(amount)?;
+        } else {
+            self.transfer_quote_to_creator(amount)?;
+        }
+
+        emit!(CreatorFeesClaimed {
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            quote_mint: self.launch_pad_token.quote_mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    fn transfer_quote_to_creator(&self, amount: u64) -> Result<()> {
+        let (
+            Some(quote_mint),
+            Some(creator_fee_quote_account),
+            Some(creator_quote_account),
+            Some(quote_token_program),
+        ) = (
+            &self.quote_mint,
+            &self.creator_fee_quote_account,
+            &self.creator_quote_account,
+            &self.quote_token_program,
+        )
+        else {
+            return err!(LaunchPadErrorCode::MissingQuoteAccounts);
+        };
+
+        let signer: &[&[&[u8]]] = &[&[
+            LaunchPadToken::SEED,
+            self.mint.to_account_info().key.as_ref(),
+            &[self.launch_pad_token.bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: creator_fee_quote_account.to_account_info(),
+                    to: creator_quote_account.to_account_info(),
+                    authority: self.launch_pad_token.to_account_info(),
+                    mint: quote_mint.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+            quote_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    ctx.accounts.claim()?;
+    msg!("Creator fees claimed");
+    Ok(())
+}
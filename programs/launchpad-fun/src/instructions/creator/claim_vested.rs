@@ -0,0 +1,98 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{LaunchPadErrorCode, LaunchPadToken, VestedTokensClaimed, Vesting};
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        bump = launch_pad_token.bump,
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+
+    #[account(
+        mut,
+        seeds = [Vesting::SEED, mint.key().as_ref()],
+        bump = vesting.bump,
+        has_one = creator @ LaunchPadErrorCode::InvalidCreator,
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn claim(&mut self) -> Result<()> {
+        let amount = self.vesting.claim(
+            self.launch_pad_token.graduated_at,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        let signer: &[&[&[u8]]] = &[&[
+            Vesting::SEED,
+            self.mint.to_account_info().key.as_ref(),
+            &[self.vesting.bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.vesting_token_account.to_account_info(),
+                    to: self.creator_token_account.to_account_info(),
+                    authority: self.vesting.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        emit!(VestedTokensClaimed {
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            amount,
+            claimed_amount: self.vesting.claimed_amount,
+            total_amount: self.vesting.total_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ClaimVested>) -> Result<()> {
+    ctx.accounts.claim()?;
+    msg!("Vested tokens claimed");
+    Ok(())
+}
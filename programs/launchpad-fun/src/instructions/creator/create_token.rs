@@ -6,23 +6,31 @@ use anchor_spl::token_interface::Mint;
 use anchor_lang::solana_program::rent::{
     DEFAULT_EXEMPTION_THRESHOLD, DEFAULT_LAMPORTS_PER_BYTE_YEAR,
 };
-use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::system_program::{create_account, transfer, CreateAccount, Transfer};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{self, AssociatedToken},
+    metadata::{
+        create_metadata_accounts_v3,
+        mpl_token_metadata::{self, types::DataV2},
+        CreateMetadataAccountsV3, Metadata,
+    },
     token_2022,
     token_interface::{
-        spl_token_2022::instruction::AuthorityType, token_metadata_initialize, Token2022,
-        TokenAccount, TokenMetadataInitialize,
+        self, metadata_pointer_initialize,
+        spl_token_2022::{self, extension::ExtensionType, instruction::AuthorityType},
+        spl_token_metadata_interface::state::Field,
+        token_metadata_initialize, token_metadata_update_field, MetadataPointerInitialize,
+        TokenInterface, TokenMetadataInitialize, TokenMetadataUpdateField,
     },
 };
 use spl_token_metadata_interface::state::TokenMetadata;
 use spl_type_length_value::variable_len_pack::VariableLenPack;
 
 use crate::{
-    initial_virtual_asset_reserve, LaunchPadConfig, LaunchPadErrorCode, LaunchPadToken,
-    ProtocolStatus, MAX_TOKEN_NAME_LENGTH, MAX_TOKEN_SYMBOL_LENGTH, MAX_TOKEN_URI_LENGTH,
-    MIN_TOKEN_NAME_LENGTH, MIN_TOKEN_SYMBOL_LENGTH, MIN_TOKEN_URI_LENGTH, TOKEN_GRADUATION_AMOUNT,
-    TOKEN_TOTAL_SUPPLY,
+    AdditionalMetadata, Allowlist, Auction, AuctionSchedule, BuyLeg, BuyerRecord, CurveType,
+    LaunchPadConfig, LaunchPadErrorCode, LaunchPadToken, Vesting, VestingCreated, VestingSchedule,
+    MAX_TOKEN_NAME_LENGTH, MAX_TOKEN_SYMBOL_LENGTH, MAX_TOKEN_URI_LENGTH, MIN_TOKEN_NAME_LENGTH,
+    MIN_TOKEN_SYMBOL_LENGTH, MIN_TOKEN_URI_LENGTH,
 };
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -30,9 +38,28 @@ pub struct CreateTokenArgs {
     pub name: String,
     pub symbol: String,
     pub uri: String,
+    pub curve_type: CurveType,
+    // Total supply in whole tokens
+    pub token_supply: u64,
+    pub decimals: u8,
+    // Share of the supply reserved for the LP at graduation, in parts per 1_000_000
+    pub graduation_allocation: u32,
+    // Lamports the creator spends on the first curve purchase, if any
+    pub initial_buy_lamports: Option<u64>,
+    // Unix timestamp at which trading opens, immediately if not set
+    pub trading_starts_at: Option<i64>,
+    // Allowlist presale run from the trading start until its end time
+    pub allowlist: Option<Allowlist>,
+    // Open the launch with a sealed-bid batch auction instead of immediate trading
+    pub auction_schedule: Option<AuctionSchedule>,
+    // Team allocation minted alongside the curve supply and vested from graduation
+    pub vesting_schedule: Option<VestingSchedule>,
+    // Extra key/value metadata such as website or socials, keys must be allowed by the config
+    pub additional_metadata: Vec<AdditionalMetadata>,
 }
 
 #[derive(Accounts)]
+#[instruction(args: CreateTokenArgs)]
 pub struct CreateToken<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -43,52 +70,125 @@ pub struct CreateToken<'info> {
     )]
     pub launch_pad_config: Account<'info, LaunchPadConfig>,
 
+    // Created as a Token-2022 mint with on-mint metadata, or as a classic SPL Token mint with
+    // Metaplex metadata when `token_program` is the SPL Token program
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
     #[account(
         init,
         payer = creator,
-        mint::decimals = 9,
-        mint::authority = launch_pad_config,
-        extensions::metadata_pointer::authority = launch_pad_config,
-        extensions::metadata_pointer::metadata_address = mint,
+        space = LaunchPadToken::DISCRIMINATOR.len() + LaunchPadToken::INIT_SPACE,
+        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+
+    /// CHECK: the launch pad's associated token account, created once the mint exists
+    #[account(
+        mut,
+        seeds = [
+            launch_pad_config.key().as_ref(),
+            token_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = associated_token_program,
+        bump,
+    )]
+    pub launch_pad_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadToken::VAULT_SEED, mint.key().as_ref()],
+        bump,
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
+    pub vault_graduation: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::VAULT_SEED],
+        bump = launch_pad_config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    // The accounts below are only required with an initial buy
+    /// CHECK: the creator's associated token account, created once the mint exists
+    #[account(
+        mut,
+        seeds = [
+            creator.key().as_ref(),
+            token_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = associated_token_program,
+        bump,
+    )]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
 
     #[account(
         init,
         payer = creator,
-        space = LaunchPadToken::DISCRIMINATOR.len() + LaunchPadToken::INIT_SPACE,
-        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        space = BuyerRecord::DISCRIMINATOR.len() + BuyerRecord::INIT_SPACE,
+        seeds = [BuyerRecord::SEED, mint.key().as_ref(), creator.key().as_ref()],
         bump
     )]
-    pub launch_pad_token: Account<'info, LaunchPadToken>,
+    pub buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+
+    // Only required for auction launches
+    #[account(
+        init,
+        payer = creator,
+        space = Auction::DISCRIMINATOR.len() + Auction::INIT_SPACE,
+        seeds = [Auction::SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub auction: Option<Box<Account<'info, Auction>>>,
 
+    // Only required with a team vesting allocation
     #[account(
         init,
         payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = launch_pad_config,
-        associated_token::token_program = token_program,
+        space = Vesting::DISCRIMINATOR.len() + Vesting::INIT_SPACE,
+        seeds = [Vesting::SEED, mint.key().as_ref()],
+        bump
     )]
-    pub launch_pad_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
 
+    /// CHECK: the vesting associated token account, its address is checked on creation by the
+    /// associated token program
+    #[account(mut)]
+    pub vesting_token_account: Option<UncheckedAccount<'info>>,
+
+    // Only required for classic SPL Token launches
+    /// CHECK: the Metaplex metadata account, initialized by the token metadata program
     #[account(
         mut,
-        seeds = [LaunchPadToken::VAULT_SEED, mint.key().as_ref()],
+        seeds = [
+            b"metadata",
+            mpl_token_metadata::ID.as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = mpl_token_metadata::ID,
         bump,
     )]
-    pub vault_graduation: SystemAccount<'info>,
+    pub metadata: Option<UncheckedAccount<'info>>,
+    pub metadata_program: Option<Program<'info, Metadata>>,
+
+    // The allowlisted SPL mint the token is priced in, native SOL when omitted
+    pub quote_mint: Option<InterfaceAccount<'info, Mint>>,
 
-    pub token_program: Program<'info, Token2022>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> CreateToken<'info> {
     pub fn create(&mut self, args: CreateTokenArgs, bumps: CreateTokenBumps) -> Result<()> {
-        require!(
-            self.launch_pad_config.status == ProtocolStatus::Active,
-            LaunchPadErrorCode::ProtocolConfigNotActive
-        );
+        let quote_mint = self.launch_pad_config.require_launch_allowed(
+            &args.curve_type,
+            self.quote_mint.as_ref().map(|quote_mint| quote_mint.key()),
+        )?;
         require!(
             args.name.len() >= MIN_TOKEN_NAME_LENGTH && args.name.len() <= MAX_TOKEN_NAME_LENGTH,
             LaunchPadErrorCode::InvalidTokenNameLength
@@ -102,34 +202,306 @@ impl<'info> CreateToken<'info> {
             args.uri.len() >= MIN_TOKEN_URI_LENGTH && args.uri.len() <= MAX_TOKEN_URI_LENGTH,
             LaunchPadErrorCode::InvalidTokenUriLength
         );
+        self.launch_pad_config
+            .metadata_rules
+            .require_allowed(&args.additional_metadata)?;
+        require!(
+            self.is_token_2022() || args.additional_metadata.is_empty(),
+            LaunchPadErrorCode::AdditionalMetadataNotSupported
+        );
+        let (token_supply, graduation_token_amount) = self
+            .launch_pad_config
+            .calculate_token_supply(args.token_supply, args.decimals, args.graduation_allocation)?;
         let launch_pad_config_bump = bumps.launch_pad_config;
-        self.init_mint_account(&args)?;
-        self.init_token_metadata(&args, launch_pad_config_bump)?;
-        self.mint_tokens(launch_pad_config_bump)?;
+        self.create_mint(args.decimals)?;
+        if self.is_token_2022() {
+            self.init_mint_account(&args)?;
+            self.init_token_metadata(&args, launch_pad_config_bump)?;
+        } else {
+            self.init_metaplex_metadata(&args, launch_pad_config_bump)?;
+        }
+        self.create_token_accounts()?;
+        let vesting_amount = match args.vesting_schedule {
+            Some(schedule) => self.init_vesting(token_supply, schedule, &bumps)?,
+            None => 0,
+        };
+        self.mint_tokens(token_supply, vesting_amount, launch_pad_config_bump)?;
         self.init_vault_account()?;
 
+        let initial_asset_reserve = args.curve_type.initial_virtual_asset_reserve(
+            self.launch_pad_config.asset_rate,
+            token_supply as u128,
+        )?;
         let initial_asset_reserve =
-            initial_virtual_asset_reserve(self.launch_pad_config.asset_rate);
+            u64::try_from(initial_asset_reserve).map_err(|_| LaunchPadErrorCode::MathOverflow)?;
         self.launch_pad_token.create(
             self.creator.key(),
             self.mint.key(),
-            TOKEN_TOTAL_SUPPLY as u64,
-            initial_asset_reserve as u64,
+            quote_mint,
+            token_supply,
+            args.decimals,
+            graduation_token_amount,
+            initial_asset_reserve,
+            args.curve_type,
+            args.trading_starts_at,
+            args.allowlist,
             bumps.launch_pad_token,
             bumps.vault_graduation,
         )?;
 
+        // Set up the auction first so the dev-buy sees the launch is held back for it
+        if let Some(schedule) = args.auction_schedule {
+            let (Some(auction), Some(auction_bump)) = (&mut self.auction, bumps.auction) else {
+                return err!(LaunchPadErrorCode::MissingAuctionAccounts);
+            };
+            auction.init(self.mint.key(), schedule, auction_bump)?;
+            self.launch_pad_token
+                .start_auction(&self.launch_pad_config)?;
+        }
+
+        if let Some(amount) = args.initial_buy_lamports.filter(|amount| *amount > 0) {
+            self.initial_buy(amount, &bumps)?;
+        }
+
+        Ok(())
+    }
+
+    /// Buys on the curve for the creator through the same fee split as `buy_token`, at the
+    /// base buy fee since the launch fee schedule starts with the launch itself.
+    fn initial_buy(&mut self, amount: u64, bumps: &CreateTokenBumps) -> Result<()> {
+        require!(
+            self.launch_pad_token.is_native_quote(),
+            LaunchPadErrorCode::InitialBuyRequiresNativeQuote
+        );
+        self.launch_pad_token.require_initial_buy_allowed()?;
+        let (Some(creator_token_account), Some(buyer_record)) =
+            (&self.creator_token_account, &mut self.buyer_record)
+        else {
+            return err!(LaunchPadErrorCode::MissingInitialBuyAccounts);
+        };
+
+        let quote =
+            self.launch_pad_token
+                .quote_buy_at_base_fee(&self.launch_pad_config, amount, true)?;
+        let max_amount = self
+            .launch_pad_config
+            .anti_snipe
+            .max_wallet_amount(self.launch_pad_token.token_supply, 0)?;
+        buyer_record.record_buy(
+            self.mint.key(),
+            self.creator.key(),
+            bumps
+                .buyer_record
+                .ok_or(LaunchPadErrorCode::MissingInitialBuyAccounts)?,
+            quote.amount_out,
+            max_amount,
+        )?;
+
+        let split = quote.buy_split(&self.launch_pad_config, false)?;
+
+        let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[bumps.launch_pad_config]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.launch_pad_token_account.to_account_info(),
+                    to: creator_token_account.to_account_info(),
+                    authority: self.launch_pad_config.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+                signer,
+            ),
+            quote.amount_out,
+            self.launch_pad_token.decimals,
+        )?;
+        split.pay(|leg, amount| match leg {
+            BuyLeg::Curve => {
+                self.transfer_from_creator(amount, self.vault_graduation.to_account_info())
+            }
+            BuyLeg::Protocol => self.transfer_from_creator(amount, self.vault.to_account_info()),
+            // The creator share is held on the launch pad token until claimed
+            BuyLeg::Creator => {
+                self.transfer_from_creator(amount, self.launch_pad_token.to_account_info())
+            }
+            // Dev-buys are never referred
+            BuyLeg::Referral => Ok(()),
+        })?;
+
+        self.launch_pad_token
+            .apply_buy(&quote, &split, self.creator.key())
+    }
+
+    fn transfer_from_creator(&self, amount: u64, destination: AccountInfo<'info>) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.creator.to_account_info(),
+                    to: destination,
+                },
+            ),
+            amount,
+        )?;
+        Ok(())
+    }
+
+    fn is_token_2022(&self) -> bool {
+        self.token_program.key() == token_2022::ID
+    }
+
+    /// Creates the mint owned by `token_program`, with the metadata pointer extension on
+    /// Token-2022 so the metadata can live on the mint itself.
+    fn create_mint(&self, decimals: u8) -> Result<()> {
+        let extensions: &[ExtensionType] = if self.is_token_2022() {
+            &[ExtensionType::MetadataPointer]
+        } else {
+            &[]
+        };
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)?;
+        create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: self.mint.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &self.token_program.key(),
+        )?;
+
+        if self.is_token_2022() {
+            metadata_pointer_initialize(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    MetadataPointerInitialize {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                ),
+                Some(self.launch_pad_config.key()),
+                Some(self.mint.key()),
+            )?;
+        }
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token_interface::InitializeMint2 {
+                    mint: self.mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &self.launch_pad_config.key(),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Creates the associated token accounts passed in, they need the mint to exist first.
+    fn create_token_accounts(&self) -> Result<()> {
+        self.create_token_account(
+            self.launch_pad_token_account.to_account_info(),
+            self.launch_pad_config.to_account_info(),
+        )?;
+        if let Some(creator_token_account) = &self.creator_token_account {
+            self.create_token_account(
+                creator_token_account.to_account_info(),
+                self.creator.to_account_info(),
+            )?;
+        }
+        if let (Some(vesting_token_account), Some(vesting)) =
+            (&self.vesting_token_account, &self.vesting)
+        {
+            self.create_token_account(
+                vesting_token_account.to_account_info(),
+                vesting.to_account_info(),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn create_token_account(
+        &self,
+        token_account: AccountInfo<'info>,
+        authority: AccountInfo<'info>,
+    ) -> Result<()> {
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.creator.to_account_info(),
+                associated_token: token_account,
+                authority,
+                mint: self.mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))
+    }
+
+    fn init_metaplex_metadata(
+        &self,
+        args: &CreateTokenArgs,
+        launch_pad_config_bump: u8,
+    ) -> Result<()> {
+        let (Some(metadata), Some(metadata_program)) = (&self.metadata, &self.metadata_program)
+        else {
+            return err!(LaunchPadErrorCode::MissingMetaplexAccounts);
+        };
+        let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: metadata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    mint_authority: self.launch_pad_config.to_account_info(),
+                    payer: self.creator.to_account_info(),
+                    update_authority: self.launch_pad_config.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer,
+            ),
+            DataV2 {
+                name: args.name.clone(),
+                symbol: args.symbol.clone(),
+                uri: args.uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )?;
         Ok(())
     }
 
     fn init_mint_account(&self, args: &CreateTokenArgs) -> Result<()> {
-        let CreateTokenArgs { name, symbol, uri } = args;
+        let CreateTokenArgs {
+            name,
+            symbol,
+            uri,
+            additional_metadata,
+            ..
+        } = args;
 
         // Define token metadata
         let token_metadata = TokenMetadata {
             name: name.clone(),
             symbol: symbol.clone(),
             uri: uri.clone(),
+            additional_metadata: additional_metadata
+                .iter()
+                .map(|field| (field.key.clone(), field.value.clone()))
+                .collect(),
             ..Default::default()
         };
 
@@ -163,7 +535,13 @@ impl<'info> CreateToken<'info> {
         args: &CreateTokenArgs,
         launch_pad_config_bump: u8,
     ) -> Result<()> {
-        let CreateTokenArgs { name, symbol, uri } = args;
+        let CreateTokenArgs {
+            name,
+            symbol,
+            uri,
+            additional_metadata,
+            ..
+        } = args;
         let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
         token_metadata_initialize(
             CpiContext::new_with_signer(
@@ -181,30 +559,101 @@ impl<'info> CreateToken<'info> {
             symbol.clone(),
             uri.clone(),
         )?;
+
+        // The mint already holds rent for these fields, see `init_mint_account`
+        for field in additional_metadata {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TokenMetadataUpdateField {
+                        program_id: self.token_program.to_account_info(),
+                        metadata: self.mint.to_account_info(),
+                        update_authority: self.launch_pad_config.to_account_info(),
+                    },
+                    signer,
+                ),
+                Field::Key(field.key.clone()),
+                field.value.clone(),
+            )?;
+        }
         Ok(())
     }
 
-    fn mint_tokens(&self, launch_pad_config_bump: u8) -> Result<()> {
+    fn init_vesting(
+        &mut self,
+        token_supply: u64,
+        schedule: VestingSchedule,
+        bumps: &CreateTokenBumps,
+    ) -> Result<u64> {
+        let (Some(vesting), Some(vesting_bump)) = (&mut self.vesting, bumps.vesting) else {
+            return err!(LaunchPadErrorCode::MissingVestingAccounts);
+        };
+        let total_amount = self
+            .launch_pad_config
+            .calculate_vesting_amount(token_supply, schedule.share)?;
+        vesting.init(
+            self.mint.key(),
+            self.creator.key(),
+            total_amount,
+            schedule,
+            vesting_bump,
+        )?;
+
+        emit!(VestingCreated {
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            total_amount,
+            cliff: schedule.cliff,
+            duration: schedule.duration,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(total_amount)
+    }
+
+    fn mint_tokens(
+        &self,
+        amount: u64,
+        vesting_amount: u64,
+        launch_pad_config_bump: u8,
+    ) -> Result<()> {
         let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
 
-        token_2022::mint_to(
+        if vesting_amount > 0 {
+            let Some(vesting_token_account) = &self.vesting_token_account else {
+                return err!(LaunchPadErrorCode::MissingVestingAccounts);
+            };
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token_interface::MintTo {
+                        mint: self.mint.to_account_info(),
+                        to: vesting_token_account.to_account_info(),
+                        authority: self.launch_pad_config.to_account_info(),
+                    },
+                    signer,
+                ),
+                vesting_amount,
+            )?;
+        }
+
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                token_2022::MintTo {
+                token_interface::MintTo {
                     mint: self.mint.to_account_info(),
                     to: self.launch_pad_token_account.to_account_info(),
                     authority: self.launch_pad_config.to_account_info(),
                 },
                 signer,
             ),
-            TOKEN_TOTAL_SUPPLY as u64,
+            amount,
         )?;
 
         // Freeze the mint authority so no more tokens can be minted to make it an NFT
-        token_2022::set_authority(
+        token_interface::set_authority(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                token_2022::SetAuthority {
+                token_interface::SetAuthority {
                     current_authority: self.launch_pad_config.to_account_info(),
                     account_or_mint: self.mint.to_account_info(),
                 },
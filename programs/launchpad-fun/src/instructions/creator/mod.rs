@@ -1,2 +1,9 @@
+pub mod claim_creator_fees;
+pub mod claim_vested;
 pub mod create_token;
+pub mod update_token_metadata;
+
+pub use claim_creator_fees::*;
+pub use claim_vested::*;
 pub use create_token::*;
+pub use update_token_metadata::*;
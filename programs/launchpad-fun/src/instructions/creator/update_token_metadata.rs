@@ -0,0 +1,233 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::{self, types::DataV2},
+        update_metadata_accounts_v2, Metadata, MetadataAccount, UpdateMetadataAccountsV2,
+    },
+    token_2022,
+    token_interface::{
+        spl_token_metadata_interface::state::Field, token_metadata_update_field, Mint,
+        TokenInterface, TokenMetadataUpdateField,
+    },
+};
+
+use crate::{
+    LaunchPadConfig, LaunchPadErrorCode, LaunchPadToken, LaunchPadTokenStatus,
+    TokenMetadataUpdated, MAX_TOKEN_NAME_LENGTH, MAX_TOKEN_SYMBOL_LENGTH, MAX_TOKEN_URI_LENGTH,
+    MIN_TOKEN_NAME_LENGTH, MIN_TOKEN_SYMBOL_LENGTH, MIN_TOKEN_URI_LENGTH,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateTokenMetadataArgs {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    // Pays for the mint account growing with longer metadata
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    // Token-2022 launches keep their metadata on the mint
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        bump = launch_pad_token.bump,
+        has_one = creator @ LaunchPadErrorCode::InvalidCreator,
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+
+    // Only required for classic SPL Token launches
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            mpl_token_metadata::ID.as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = mpl_token_metadata::ID,
+        bump,
+    )]
+    pub metadata: Option<Box<Account<'info, MetadataAccount>>>,
+    pub metadata_program: Option<Program<'info, Metadata>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateTokenMetadata<'info> {
+    pub fn update(
+        &mut self,
+        args: UpdateTokenMetadataArgs,
+        bumps: UpdateTokenMetadataBumps,
+    ) -> Result<()> {
+        require!(
+            self.launch_pad_token.status == LaunchPadTokenStatus::TradingEnabled,
+            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled
+        );
+
+        let mut fields = Vec::new();
+        if let Some(name) = args.name {
+            require!(
+                name.len() >= MIN_TOKEN_NAME_LENGTH && name.len() <= MAX_TOKEN_NAME_LENGTH,
+                LaunchPadErrorCode::InvalidTokenNameLength
+            );
+            fields.push((Field::Name, name));
+        }
+        if let Some(symbol) = args.symbol {
+            require!(
+                symbol.len() >= MIN_TOKEN_SYMBOL_LENGTH && symbol.len() <= MAX_TOKEN_SYMBOL_LENGTH,
+                LaunchPadErrorCode::InvalidTokenSymbolLength
+            );
+            fields.push((Field::Symbol, symbol));
+        }
+        if let Some(uri) = args.uri {
+            require!(
+                uri.len() >= MIN_TOKEN_URI_LENGTH && uri.len() <= MAX_TOKEN_URI_LENGTH,
+                LaunchPadErrorCode::InvalidTokenUriLength
+            );
+            fields.push((Field::Uri, uri));
+        }
+        require!(
+            !fields.is_empty(),
+            LaunchPadErrorCode::EmptyTokenMetadataUpdate
+        );
+
+        if self.token_program.key() != token_2022::ID {
+            return self.update_metaplex_metadata(fields, bumps.launch_pad_config);
+        }
+        for (field, value) in fields {
+            self.update_field(field, value, bumps.launch_pad_config)?;
+        }
+        self.top_up_mint_rent()
+    }
+
+    fn update_field(&self, field: Field, value: String, launch_pad_config_bump: u8) -> Result<()> {
+        let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: self.mint.to_account_info(),
+                    update_authority: self.launch_pad_config.to_account_info(),
+                },
+                signer,
+            ),
+            field.clone(),
+            value.clone(),
+        )?;
+
+        self.emit_update(&field, value)
+    }
+
+    /// Classic SPL Token launches keep their metadata in a Metaplex account, rewritten whole
+    /// with the updated fields.
+    fn update_metaplex_metadata(
+        &self,
+        fields: Vec<(Field, String)>,
+        launch_pad_config_bump: u8,
+    ) -> Result<()> {
+        let (Some(metadata), Some(metadata_program)) = (&self.metadata, &self.metadata_program)
+        else {
+            return err!(LaunchPadErrorCode::MissingMetaplexAccounts);
+        };
+
+        // Metaplex pads stored strings with zeros
+        let mut data = DataV2 {
+            name: metadata.name.trim_end_matches('\0').to_string(),
+            symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: metadata.uri.trim_end_matches('\0').to_string(),
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        };
+        for (field, value) in &fields {
+            match field {
+                Field::Name => data.name = value.clone(),
+                Field::Symbol => data.symbol = value.clone(),
+                Field::Uri => data.uri = value.clone(),
+                Field::Key(_) => return err!(LaunchPadErrorCode::AdditionalMetadataNotSupported),
+            }
+        }
+
+        let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: metadata.to_account_info(),
+                    update_authority: self.launch_pad_config.to_account_info(),
+                },
+                signer,
+            ),
+            None,
+            Some(data),
+            None,
+            None,
+        )?;
+
+        for (field, value) in fields {
+            self.emit_update(&field, value)?;
+        }
+        Ok(())
+    }
+
+    fn emit_update(&self, field: &Field, value: String) -> Result<()> {
+        let field_name = match field {
+            Field::Name => "name".to_string(),
+            Field::Symbol => "symbol".to_string(),
+            Field::Uri => "uri".to_string(),
+            Field::Key(key) => key.clone(),
+        };
+        emit!(TokenMetadataUpdated {
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            field: field_name,
+            value,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Token-2022 reallocates the mint for the new metadata, the creator covers any extra rent.
+    fn top_up_mint_rent(&self) -> Result<()> {
+        let mint = self.mint.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(mint.data_len());
+        let lamports = rent_exempt.saturating_sub(mint.lamports());
+        if lamports == 0 {
+            return Ok(());
+        }
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.creator.to_account_info(),
+                    to: mint,
+                },
+            ),
+            lamports,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateTokenMetadata>, args: UpdateTokenMetadataArgs) -> Result<()> {
+    ctx.accounts.update(args, ctx.bumps)?;
+    msg!("Token metadata updated");
+    Ok(())
+}
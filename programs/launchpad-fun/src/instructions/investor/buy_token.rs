@@ -3,31 +3,33 @@ use anchor_lang::prelude::*;
 
 use anchor_spl::token_interface::Mint;
 
-use anchor_lang::solana_program::rent::{
-    DEFAULT_EXEMPTION_THRESHOLD, DEFAULT_LAMPORTS_PER_BYTE_YEAR,
-};
 use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022,
-    token_interface::{
-        spl_token_2022::instruction::AuthorityType, token_metadata_initialize, Token2022,
-        TokenAccount, TokenMetadataInitialize,
-    },
+    token_interface::{self, TokenAccount, TokenInterface, TransferChecked},
 };
-use spl_token_metadata_interface::state::TokenMetadata;
-use spl_type_length_value::variable_len_pack::VariableLenPack;
 
 use crate::{
-    calc_token_amount_out, initial_virtual_asset_reserve, LaunchPadConfig, LaunchPadErrorCode,
-    LaunchPadToken, LaunchPadTokenStatus, ProtocolStatus, MAX_TOKEN_NAME_LENGTH,
-    MAX_TOKEN_SYMBOL_LENGTH, MAX_TOKEN_URI_LENGTH, MIN_TOKEN_NAME_LENGTH, MIN_TOKEN_SYMBOL_LENGTH,
-    MIN_TOKEN_URI_LENGTH, TOKEN_GRADUATION_AMOUNT, TOKEN_TOTAL_SUPPLY,
+    AllowlistProof, BuyLeg, BuyerRecord, LaunchPadConfig, LaunchPadErrorCode, LaunchPadToken,
+    Referral, ReferralFeesAccrued, TradeQuote,
 };
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct BuyTokenArgs {
     pub amount: u64,
+    pub min_token_out: u64,
+    // Revert instead of partially filling a buy that crosses the graduation cap
+    pub strict_fill: bool,
+    // Required while the token's allowlist presale is running
+    pub allowlist_proof: Option<AllowlistProof>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct BuyTokenExactOutArgs {
+    pub token_amount: u64,
+    pub max_asset_in: u64,
+    // Required while the token's allowlist presale is running
+    pub allowlist_proof: Option<AllowlistProof>,
 }
 
 #[derive(Accounts)]
@@ -82,70 +84,198 @@ pub struct BuyToken<'info> {
     )]
     pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token2022>,
+    // Cumulative tokens bought by the investor, capped during the anti-snipe window
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = BuyerRecord::DISCRIMINATOR.len() + BuyerRecord::INIT_SPACE,
+        seeds = [BuyerRecord::SEED, mint.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Box<Account<'info, BuyerRecord>>,
+
+    // The accounts below are only required when the token is priced in an SPL quote mint
+    #[account(
+        address = launch_pad_token.quote_mint @ LaunchPadErrorCode::InvalidQuoteMint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub investor_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault_graduation,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub fee_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = launch_pad_token,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub creator_fee_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    // The referral credited with a share of the protocol fee, if the trade was referred
+    #[account(
+        mut,
+        seeds = [
+            Referral::SEED,
+            referral.referrer.as_ref(),
+            launch_pad_token.quote_mint.as_ref(),
+        ],
+        bump = referral.bump,
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = referral,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub referral_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> BuyToken<'info> {
     pub fn buy_token(&mut self, args: BuyTokenArgs, bumps: BuyTokenBumps) -> Result<()> {
+        self.launch_pad_token
+            .open_trading(&self.launch_pad_config)?;
+
+        let BuyTokenArgs {
+            amount,
+            min_token_out,
+            strict_fill,
+            allowlist_proof,
+        } = args;
+
+        let quote =
+            self.launch_pad_token
+                .quote_buy(&self.launch_pad_config, amount, strict_fill)?;
+
         require!(
-            self.launch_pad_config.status == ProtocolStatus::Active,
-            LaunchPadErrorCode::ProtocolConfigNotActive
-        );
-        require!(
-            self.launch_pad_token.status == LaunchPadTokenStatus::TradingEnabled,
-            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled
+            quote.amount_out >= min_token_out,
+            LaunchPadErrorCode::SlippageExceeded
         );
 
-        let BuyTokenArgs { amount } = args;
-        let launch_pad_config_bump = bumps.launch_pad_config;
-        let current_asset_supply = self.launch_pad_token.virtual_asset_amount;
-        let current_token_supply = self.launch_pad_token.virtual_token_amount;
-        let current_k = self.launch_pad_token.current_k;
+        self.settle_buy(&quote, allowlist_proof, &bumps)
+    }
 
-        let buy_fee = self.launch_pad_config.calculate_buy_fee(amount)?;
-        let amount = amount
-            .checked_sub(buy_fee)
-            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    pub fn buy_token_exact_out(
+        &mut self,
+        args: BuyTokenExactOutArgs,
+        bumps: BuyTokenBumps,
+    ) -> Result<()> {
+        self.launch_pad_token
+            .open_trading(&self.launch_pad_config)?;
 
-        let token_amount_out = calc_token_amount_out(
-            amount,
-            current_k,
-            current_asset_supply as u128,
-            current_token_supply as u128,
-        )? as u64;
+        let BuyTokenExactOutArgs {
+            token_amount,
+            max_asset_in,
+            allowlist_proof,
+        } = args;
 
-        let total_supply_minus_graduation = current_token_supply
-            .checked_sub(TOKEN_GRADUATION_AMOUNT as u64)
-            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let quote = self
+            .launch_pad_token
+            .quote_buy_exact_out(&self.launch_pad_config, token_amount)?;
 
         require!(
-            token_amount_out <= total_supply_minus_graduation,
-            LaunchPadErrorCode::InsufficientTokenLiquidity
+            quote.amount_in <= max_asset_in,
+            LaunchPadErrorCode::SlippageExceeded
         );
 
-        self.transfer_tokens_to_investor(token_amount_out, launch_pad_config_bump)?;
-        self.transfer_assets_from_investor_to(amount, &self.vault_graduation.to_account_info())?;
-        self.transfer_assets_from_investor_to(buy_fee, &self.vault.to_account_info())?;
-
-        self.launch_pad_token.update_virtual_reserves(
-            current_token_supply
-                .checked_sub(token_amount_out)
-                .ok_or(LaunchPadErrorCode::MathOverflow)?,
-            current_asset_supply
-                .checked_add(amount)
-                .ok_or(LaunchPadErrorCode::MathOverflow)?,
-        )?;
-
-        self.launch_pad_token
-            .increase_virtual_graduation_amount(amount)?;
+        self.settle_buy(&quote, allowlist_proof, &bumps)
+    }
 
-        if self.launch_pad_token.virtual_graduation_amount
-            >= self.launch_pad_config.graduate_threshold
-        {
+    fn settle_buy(
+        &mut self,
+        quote: &TradeQuote,
+        allowlist_proof: Option<AllowlistProof>,
+        bumps: &BuyTokenBumps,
+    ) -> Result<()> {
+        self.record_buy(quote.amount_out, bumps.buyer_record)?;
+        if self.launch_pad_token.is_allowlist_active()? {
+            let allowlist_proof =
+                allowlist_proof.ok_or(LaunchPadErrorCode::AllowlistProofRequired)?;
             self.launch_pad_token
-                .update_status(LaunchPadTokenStatus::ReadyToGraduate)?;
+                .verify_allowlist_proof(&self.investor.key(), &allowlist_proof)?;
+            self.buyer_record
+                .claim_allowlist(quote.amount_out, allowlist_proof.allocation)?;
+        }
+
+        if let Some(referral) = &self.referral {
+            referral.require_not_self_referral(self.investor.key())?;
+        }
+        let split = quote.buy_split(&self.launch_pad_config, self.referral.is_some())?;
+
+        self.transfer_tokens_to_investor(quote.amount_out, bumps.launch_pad_config)?;
+        split.pay(|leg, amount| match leg {
+            BuyLeg::Curve => self.transfer_from_investor(
+                amount,
+                self.vault_graduation.to_account_info(),
+                &self.vault_quote_account,
+            ),
+            BuyLeg::Protocol => self.transfer_from_investor(
+                amount,
+                self.vault.to_account_info(),
+                &self.fee_quote_account,
+            ),
+            BuyLeg::Creator => self.transfer_from_investor(
+                amount,
+                self.launch_pad_token.to_account_info(),
+                &self.creator_fee_quote_account,
+            ),
+            BuyLeg::Referral => match &self.referral {
+                Some(referral) => self.transfer_from_investor(
+                    amount,
+                    referral.to_account_info(),
+                    &self.referral_quote_account,
+                ),
+                None => Ok(()),
+            },
+        })?;
+
+        self.launch_pad_token
+            .apply_buy(quote, &split, self.investor.key())?;
+        if let Some(referral) = &mut self.referral {
+            referral.accrue(quote.amount_in, split.referral_fee)?;
+            emit!(ReferralFeesAccrued {
+                referrer: referral.referrer,
+                trader: self.investor.key(),
+                mint: self.mint.key(),
+                volume: quote.amount_in,
+                amount: split.referral_fee,
+                lifetime_volume: referral.lifetime_volume,
+                unclaimed_amount: referral.unclaimed_fees,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
         }
 
         Ok(())
@@ -170,13 +300,77 @@ impl<'info> BuyToken<'info> {
         Ok(())
     }
 
+    fn record_buy(&mut self, token_amount: u64, buyer_record_bump: u8) -> Result<()> {
+        let elapsed = Clock::get()?
+            .unix_timestamp
+            .checked_sub(self.launch_pad_token.trading_starts_at)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let max_amount = self
+            .launch_pad_config
+            .anti_snipe
+            .max_wallet_amount(self.launch_pad_token.token_supply, elapsed)?;
+        self.buyer_record.record_buy(
+            self.mint.key(),
+            self.investor.key(),
+            buyer_record_bump,
+            token_amount,
+            max_amount,
+        )
+    }
+
+    /// Pays `amount` of the quote asset to `destination` for native launches, or to
+    /// `quote_destination` for SPL quoted launches.
+    fn transfer_from_investor(
+        &self,
+        amount: u64,
+        destination: AccountInfo<'info>,
+        quote_destination: &Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if self.launch_pad_token.is_native_quote() {
+            return self.transfer_assets_from_investor_to(amount, &destination);
+        }
+
+        let (
+            Some(quote_mint),
+            Some(investor_quote_account),
+            Some(quote_destination),
+            Some(quote_token_program),
+        ) = (
+            &self.quote_mint,
+            &self.investor_quote_account,
+            quote_destination,
+            &self.quote_token_program,
+        )
+        else {
+            return err!(LaunchPadErrorCode::MissingQuoteAccounts);
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: investor_quote_account.to_account_info(),
+                    to: quote_destination.to_account_info(),
+                    authority: self.investor.to_account_info(),
+                    mint: quote_mint.to_account_info(),
+                },
+            ),
+            amount,
+            quote_mint.decimals,
+        )?;
+        Ok(())
+    }
+
     fn transfer_tokens_to_investor(&self, amount: u64, launch_pad_config_bump: u8) -> Result<()> {
         let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
 
-        token_2022::transfer_checked(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                token_2022::TransferChecked {
+                token_interface::TransferChecked {
                     from: self.launch_pad_token_account.to_account_info(),
                     to: self.investor_token_account.to_account_info(),
                     authority: self.launch_pad_config.to_account_info(),
@@ -185,7 +379,7 @@ impl<'info> BuyToken<'info> {
                 signer,
             ),
             amount,
-            9,
+            self.mint.decimals,
         )?;
         Ok(())
     }
@@ -196,3 +390,9 @@ pub fn handler(ctx: Context<BuyToken>, args: BuyTokenArgs) -> Result<()> {
     msg!("Launch Pad token bought successfully");
     Ok(())
 }
+
+pub fn exact_out_handler(ctx: Context<BuyToken>, args: BuyTokenExactOutArgs) -> Result<()> {
+    ctx.accounts.buy_token_exact_out(args, ctx.bumps)?;
+    msg!("Launch Pad token bought successfully");
+    Ok(())
+}
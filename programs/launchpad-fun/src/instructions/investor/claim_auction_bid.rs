@@ -0,0 +1,102 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{Auction, AuctionBid, AuctionBidClaimed};
+
+#[derive(Accounts)]
+pub struct ClaimAuctionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [Auction::SEED, mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [AuctionBid::SEED, mint.key().as_ref(), bidder.key().as_ref()],
+        bump = auction_bid.bump,
+    )]
+    pub auction_bid: Account<'info, AuctionBid>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub auction_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimAuctionBid<'info> {
+    pub fn claim(&mut self) -> Result<()> {
+        let (token_amount, refund) = self
+            .auction
+            .fill(self.auction_bid.revealed_amount, self.auction_bid.deposit)?;
+
+        if token_amount > 0 {
+            let signer: &[&[&[u8]]] = &[&[
+                Auction::SEED,
+                self.mint.to_account_info().key.as_ref(),
+                &[self.auction.bump],
+            ]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.auction_token_account.to_account_info(),
+                        to: self.bidder_token_account.to_account_info(),
+                        authority: self.auction.to_account_info(),
+                        mint: self.mint.to_account_info(),
+                    },
+                    signer,
+                ),
+                token_amount,
+                self.mint.decimals,
+            )?;
+        }
+        // The unspent deposit sits on top of the auction's rent exemption
+        self.auction.sub_lamports(refund)?;
+        self.bidder.add_lamports(refund)?;
+
+        emit!(AuctionBidClaimed {
+            bidder: self.bidder.key(),
+            mint: self.mint.key(),
+            token_amount,
+            refund,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ClaimAuctionBid>) -> Result<()> {
+    ctx.accounts.claim()?;
+    msg!("Auction bid claimed");
+    Ok(())
+}
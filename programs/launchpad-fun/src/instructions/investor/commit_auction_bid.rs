@@ -0,0 +1,93 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::Mint;
+
+use crate::{Auction, AuctionBid, AuctionBidCommitted, LaunchPadConfig};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CommitAuctionBidArgs {
+    // Hash of the sealed bid, see `auction_bid_commitment`
+    pub commitment: [u8; 32],
+    // Lamports escrowed with the bid, at least the amount bid
+    pub deposit: u64,
+}
+
+#[derive(Accounts)]
+pub struct CommitAuctionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Escrows the deposits until the bids are claimed
+    #[account(
+        mut,
+        seeds = [Auction::SEED, mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = AuctionBid::DISCRIMINATOR.len() + AuctionBid::INIT_SPACE,
+        seeds = [AuctionBid::SEED, mint.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub auction_bid: Account<'info, AuctionBid>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitAuctionBid<'info> {
+    pub fn commit(
+        &mut self,
+        args: CommitAuctionBidArgs,
+        bumps: CommitAuctionBidBumps,
+    ) -> Result<()> {
+        self.launch_pad_config.require_active()?;
+        self.auction.commit(args.deposit)?;
+        self.auction_bid.commit(
+            self.mint.key(),
+            self.bidder.key(),
+            args.commitment,
+            args.deposit,
+            bumps.auction_bid,
+        )?;
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.bidder.to_account_info(),
+                    to: self.auction.to_account_info(),
+                },
+            ),
+            args.deposit,
+        )?;
+
+        emit!(AuctionBidCommitted {
+            bidder: self.bidder.key(),
+            mint: self.mint.key(),
+            deposit: args.deposit,
+            total_deposits: self.auction.total_deposits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CommitAuctionBid>, args: CommitAuctionBidArgs) -> Result<()> {
+    ctx.accounts.commit(args, ctx.bumps)?;
+    msg!("Auction bid committed");
+    Ok(())
+}
@@ -1,5 +1,15 @@
 pub mod buy_token;
+pub mod claim_auction_bid;
+pub mod commit_auction_bid;
+pub mod quote_buy;
+pub mod quote_sell;
+pub mod reveal_auction_bid;
 pub mod sell_token;
 
 pub use buy_token::*;
+pub use claim_auction_bid::*;
+pub use commit_auction_bid::*;
+pub use quote_buy::*;
+pub use quote_sell::*;
+pub use reveal_auction_bid::*;
 pub use sell_token::*;
@@ -0,0 +1,41 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadToken, TradeQuote};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct QuoteBuyArgs {
+    pub amount: u64,
+    pub strict_fill: bool,
+}
+
+#[derive(Accounts)]
+pub struct QuoteBuy<'info> {
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account(
+        seeds = [LaunchPadToken::SEED, launch_pad_token.mint.as_ref()],
+        bump = launch_pad_token.bump,
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+}
+
+impl<'info> QuoteBuy<'info> {
+    pub fn quote_buy(&self, args: QuoteBuyArgs) -> Result<TradeQuote> {
+        self.launch_pad_config.require_active()?;
+        self.launch_pad_token.require_trading_enabled()?;
+
+        self.launch_pad_token
+            .quote_buy(&self.launch_pad_config, args.amount, args.strict_fill)
+    }
+}
+
+pub fn handler(ctx: Context<QuoteBuy>, args: QuoteBuyArgs) -> Result<TradeQuote> {
+    let quote = ctx.accounts.quote_buy(args)?;
+    msg!("Launch Pad token buy quoted successfully");
+    Ok(quote)
+}
@@ -0,0 +1,40 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadToken, TradeQuote};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct QuoteSellArgs {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSell<'info> {
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account(
+        seeds = [LaunchPadToken::SEED, launch_pad_token.mint.as_ref()],
+        bump = launch_pad_token.bump,
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+}
+
+impl<'info> QuoteSell<'info> {
+    pub fn quote_sell(&self, args: QuoteSellArgs) -> Result<TradeQuote> {
+        self.launch_pad_config.require_active()?;
+        self.launch_pad_token.require_trading_enabled()?;
+
+        self.launch_pad_token
+            .quote_sell(&self.launch_pad_config, args.amount)
+    }
+}
+
+pub fn handler(ctx: Context<QuoteSell>, args: QuoteSellArgs) -> Result<TradeQuote> {
+    let quote = ctx.accounts.quote_sell(args)?;
+    msg!("Launch Pad token sell quoted successfully");
+    Ok(quote)
+}
@@ -0,0 +1,57 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{Auction, AuctionBid, AuctionBidRevealed};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RevealAuctionBidArgs {
+    // Lamports bid, spent on the curve at the clearing price
+    pub amount: u64,
+    pub salt: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct RevealAuctionBid<'info> {
+    pub bidder: Signer<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [Auction::SEED, mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [AuctionBid::SEED, mint.key().as_ref(), bidder.key().as_ref()],
+        bump = auction_bid.bump,
+    )]
+    pub auction_bid: Account<'info, AuctionBid>,
+}
+
+impl<'info> RevealAuctionBid<'info> {
+    pub fn reveal(&mut self, args: RevealAuctionBidArgs) -> Result<()> {
+        self.auction_bid.reveal(args.amount, &args.salt)?;
+        self.auction.reveal(args.amount)?;
+
+        emit!(AuctionBidRevealed {
+            bidder: self.bidder.key(),
+            mint: self.mint.key(),
+            amount: args.amount,
+            total_revealed: self.auction.total_revealed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RevealAuctionBid>, args: RevealAuctionBidArgs) -> Result<()> {
+    ctx.accounts.reveal(args)?;
+    msg!("Auction bid revealed");
+    Ok(())
+}
@@ -3,31 +3,21 @@ use anchor_lang::prelude::*;
 
 use anchor_spl::token_interface::Mint;
 
-use anchor_lang::solana_program::rent::{
-    DEFAULT_EXEMPTION_THRESHOLD, DEFAULT_LAMPORTS_PER_BYTE_YEAR,
-};
 use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022,
-    token_interface::{
-        spl_token_2022::instruction::AuthorityType, token_metadata_initialize, Token2022,
-        TokenAccount, TokenMetadataInitialize,
-    },
+    token_interface::{self, TokenAccount, TokenInterface, TransferChecked},
 };
-use spl_token_metadata_interface::state::TokenMetadata;
-use spl_type_length_value::variable_len_pack::VariableLenPack;
 
 use crate::{
-    calc_asset_amount_out, calc_token_amount_out, initial_virtual_asset_reserve, LaunchPadConfig,
-    LaunchPadErrorCode, LaunchPadToken, LaunchPadTokenStatus, ProtocolStatus,
-    MAX_TOKEN_NAME_LENGTH, MAX_TOKEN_SYMBOL_LENGTH, MAX_TOKEN_URI_LENGTH, MIN_TOKEN_NAME_LENGTH,
-    MIN_TOKEN_SYMBOL_LENGTH, MIN_TOKEN_URI_LENGTH, TOKEN_GRADUATION_AMOUNT, TOKEN_TOTAL_SUPPLY,
+    CreatorFeesAccrued, LaunchPadConfig, LaunchPadErrorCode, LaunchPadToken, LaunchPadTokenSold,
+    Referral, ReferralFeesAccrued, SellLeg,
 };
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct SellTokenArgs {
     pub amount: u64,
+    pub min_asset_out: u64,
 }
 
 #[derive(Accounts)]
@@ -82,94 +72,251 @@ pub struct SellToken<'info> {
     )]
     pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token2022>,
+    // The accounts below are only required when the token is priced in an SPL quote mint
+    #[account(
+        address = launch_pad_token.quote_mint @ LaunchPadErrorCode::InvalidQuoteMint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub investor_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault_graduation,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub fee_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = launch_pad_token,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub creator_fee_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    // The referral credited with a share of the protocol fee, if the trade was referred
+    #[account(
+        mut,
+        seeds = [
+            Referral::SEED,
+            referral.referrer.as_ref(),
+            launch_pad_token.quote_mint.as_ref(),
+        ],
+        bump = referral.bump,
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = quote_mint,
+        associated_token::authority = referral,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub referral_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SellToken<'info> {
     pub fn sell_token(&mut self, args: SellTokenArgs) -> Result<()> {
-        require!(
-            self.launch_pad_config.status == ProtocolStatus::Active,
-            LaunchPadErrorCode::ProtocolConfigNotActive
-        );
-        require!(
-            self.launch_pad_token.status == LaunchPadTokenStatus::TradingEnabled,
-            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled
-        );
+        self.launch_pad_token
+            .open_trading(&self.launch_pad_config)?;
+        self.launch_pad_token.require_creator_sell_delay_met(
+            self.investor.key(),
+            self.launch_pad_config.creator_sell_delay,
+        )?;
 
         let SellTokenArgs {
-            amount: token_amount_in,
+            amount,
+            min_asset_out,
         } = args;
         let launch_pad_vault_bump = self.launch_pad_token.vault_bump;
-        let current_asset_supply = self.launch_pad_token.virtual_asset_amount;
-        let current_token_supply = self.launch_pad_token.virtual_token_amount;
-        let current_k = self.launch_pad_token.current_k;
-
-        let asset_amount_out = calc_asset_amount_out(
-            token_amount_in,
-            current_k,
-            current_token_supply as u128,
-            current_asset_supply as u128,
-        )? as u64;
-
-        let sell_fee = self
-            .launch_pad_config
-            .calculate_sell_fee(asset_amount_out)?;
-        let asset_amount_out_with_fee = asset_amount_out
-            .checked_sub(sell_fee)
-            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+
+        let quote = self
+            .launch_pad_token
+            .quote_sell(&self.launch_pad_config, amount)?;
 
         require!(
-            asset_amount_out <= current_asset_supply,
-            LaunchPadErrorCode::InsufficientAssetLiquidity
+            quote.amount_out >= min_asset_out,
+            LaunchPadErrorCode::SlippageExceeded
         );
 
-        self.transfer_tokens_from_investor(token_amount_in)?;
-        self.transfer_assets_to_investor(asset_amount_out_with_fee, launch_pad_vault_bump)?;
-        self.transfer_sell_fee(sell_fee, launch_pad_vault_bump)?;
-
-        self.launch_pad_token.update_virtual_reserves(
-            current_token_supply
-                .checked_add(token_amount_in)
-                .ok_or(LaunchPadErrorCode::MathOverflow)?,
-            current_asset_supply
-                .checked_sub(asset_amount_out)
-                .ok_or(LaunchPadErrorCode::MathOverflow)?,
-        )?;
+        if let Some(referral) = &self.referral {
+            referral.require_not_self_referral(self.investor.key())?;
+        }
+        let split = quote.sell_split(&self.launch_pad_config, self.referral.is_some())?;
 
-        self.launch_pad_token
-            .decrease_virtual_graduation_amount(asset_amount_out)?;
+        self.transfer_tokens_from_investor(quote.amount_in)?;
+        split.pay(|leg, amount| match leg {
+            SellLeg::Trader => self.transfer_from_vault_graduation(
+                amount,
+                self.investor.to_account_info(),
+                &self.investor_quote_account,
+                launch_pad_vault_bump,
+            ),
+            SellLeg::Protocol => self.transfer_from_vault_graduation(
+                amount,
+                self.vault.to_account_info(),
+                &self.fee_quote_account,
+                launch_pad_vault_bump,
+            ),
+            // The creator share is held on the launch pad token until claimed
+            SellLeg::Creator => self.transfer_from_vault_graduation(
+                amount,
+                self.launch_pad_token.to_account_info(),
+                &self.creator_fee_quote_account,
+                launch_pad_vault_bump,
+            ),
+            SellLeg::Referral => match &self.referral {
+                Some(referral) => self.transfer_from_vault_graduation(
+                    amount,
+                    referral.to_account_info(),
+                    &self.referral_quote_account,
+                    launch_pad_vault_bump,
+                ),
+                None => Ok(()),
+            },
+        })?;
+
+        self.launch_pad_token.apply_trade(&quote)?;
+        if let Some(referral) = &mut self.referral {
+            let volume = quote
+                .amount_out
+                .checked_add(quote.fee)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?;
+            referral.accrue(volume, split.referral_fee)?;
+            emit!(ReferralFeesAccrued {
+                referrer: referral.referrer,
+                trader: self.investor.key(),
+                mint: self.mint.key(),
+                volume,
+                amount: split.referral_fee,
+                lifetime_volume: referral.lifetime_volume,
+                unclaimed_amount: referral.unclaimed_fees,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        emit!(LaunchPadTokenSold {
+            trader: self.investor.key(),
+            mint: self.mint.key(),
+            asset_amount: quote.amount_out,
+            token_amount: quote.amount_in,
+            fee: quote.fee,
+            virtual_token_amount: self.launch_pad_token.virtual_token_amount,
+            virtual_asset_amount: self.launch_pad_token.virtual_asset_amount,
+            virtual_graduation_amount: self.launch_pad_token.virtual_graduation_amount,
+            status: self.launch_pad_token.status,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if quote.creator_fee > 0 {
+            emit!(CreatorFeesAccrued {
+                creator: self.launch_pad_token.creator,
+                mint: self.mint.key(),
+                amount: quote.creator_fee,
+                unclaimed_amount: self.launch_pad_token.creator_fees,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
 
         Ok(())
     }
 
-    fn transfer_assets_to_investor(&self, amount: u64, launch_pad_vault_bump: u8) -> Result<()> {
+    /// Pays `amount` of the quote asset out of the graduation vault to `destination` for
+    /// native launches, or to `quote_destination` for SPL quoted launches.
+    fn transfer_from_vault_graduation(
+        &self,
+        amount: u64,
+        destination: AccountInfo<'info>,
+        quote_destination: &Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+        launch_pad_vault_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
         let signer: &[&[&[u8]]] = &[&[
             LaunchPadToken::VAULT_SEED,
             self.mint.to_account_info().key.as_ref(),
             &[launch_pad_vault_bump],
         ]];
-        transfer(
+        if self.launch_pad_token.is_native_quote() {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.vault_graduation.to_account_info(),
+                        to: destination,
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+            return Ok(());
+        }
+
+        let (
+            Some(quote_mint),
+            Some(vault_quote_account),
+            Some(quote_destination),
+            Some(quote_token_program),
+        ) = (
+            &self.quote_mint,
+            &self.vault_quote_account,
+            quote_destination,
+            &self.quote_token_program,
+        )
+        else {
+            return err!(LaunchPadErrorCode::MissingQuoteAccounts);
+        };
+
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                self.system_program.to_account_info(),
-                Transfer {
-                    from: self.vault_graduation.to_account_info(),
-                    to: self.investor.to_account_info(),
+                quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: vault_quote_account.to_account_info(),
+                    to: quote_destination.to_account_info(),
+                    authority: self.vault_graduation.to_account_info(),
+                    mint: quote_mint.to_account_info(),
                 },
                 signer,
             ),
             amount,
+            quote_mint.decimals,
         )?;
-
         Ok(())
     }
 
     fn transfer_tokens_from_investor(&self, amount: u64) -> Result<()> {
-        token_2022::transfer_checked(
+        token_interface::transfer_checked(
             CpiContext::new(
                 self.token_program.to_account_info(),
-                token_2022::TransferChecked {
+                token_interface::TransferChecked {
                     from: self.investor_token_account.to_account_info(),
                     to: self.launch_pad_token_account.to_account_info(),
                     authority: self.investor.to_account_info(),
@@ -177,31 +324,10 @@ impl<'info> SellToken<'info> {
                 },
             ),
             amount,
-            9,
+            self.mint.decimals,
         )?;
         Ok(())
     }
-
-    fn transfer_sell_fee(&self, amount: u64, launch_pad_vault_bump: u8) -> Result<()> {
-        let signer: &[&[&[u8]]] = &[&[
-            LaunchPadToken::VAULT_SEED,
-            self.mint.to_account_info().key.as_ref(),
-            &[launch_pad_vault_bump],
-        ]];
-        transfer(
-            CpiContext::new_with_signer(
-                self.system_program.to_account_info(),
-                Transfer {
-                    from: self.vault_graduation.to_account_info(),
-                    to: self.vault.to_account_info(),
-                },
-                signer,
-            ),
-            amount,
-        )?;
-
-        Ok(())
-    }
 }
 
 pub fn handler(ctx: Context<SellToken>, args: SellTokenArgs) -> Result<()> {
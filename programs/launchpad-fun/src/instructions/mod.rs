@@ -1,11 +1,13 @@
-#![allow(unused_imports, ambiguous_glob_reexports)]
+#![allow(ambiguous_glob_reexports)]
 
 pub mod admin;
 pub mod creator;
 pub mod investor;
+pub mod referrer;
 pub mod token;
 
 pub use admin::*;
 pub use creator::*;
 pub use investor::*;
+pub use referrer::*;
 pub use token::*;
@@ -0,0 +1,58 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, Referral, ReferralRegistered};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct InitReferralArgs {
+    // The quote mint referral fees are paid in, the wSOL mint for native SOL launches
+    pub quote_mint: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitReferralArgs)]
+pub struct InitReferral<'info> {
+    // The partner registering to receive referral fees
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump = launch_pad_config.bump,
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = Referral::DISCRIMINATOR.len() + Referral::INIT_SPACE,
+        seeds = [Referral::SEED, referrer.key().as_ref(), args.quote_mint.as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitReferral<'info> {
+    pub fn init(&mut self, args: InitReferralArgs, bumps: InitReferralBumps) -> Result<()> {
+        self.launch_pad_config
+            .require_quote_mint_allowed(&args.quote_mint)?;
+        self.referral
+            .init(self.referrer.key(), args.quote_mint, bumps.referral)?;
+
+        emit!(ReferralRegistered {
+            referrer: self.referrer.key(),
+            referral: self.referral.key(),
+            quote_mint: args.quote_mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<InitReferral>, args: InitReferralArgs) -> Result<()> {
+    ctx.accounts.init(args, ctx.bumps)?;
+    msg!("Referral registered");
+    Ok(())
+}
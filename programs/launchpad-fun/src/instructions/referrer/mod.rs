@@ -0,0 +1,5 @@
+pub mod init_referral;
+pub mod withdraw_referral_fees;
+
+pub use init_referral::*;
+pub use withdraw_referral_fees::*;
@@ -0,0 +1,120 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{LaunchPadErrorCode, Referral, ReferralFeesWithdrawn, NATIVE_MINT};
+
+#[derive(Accounts)]
+pub struct WithdrawReferralFees<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Referral::SEED, referrer.key().as_ref(), referral.quote_mint.as_ref()],
+        bump = referral.bump,
+        has_one = referrer @ LaunchPadErrorCode::InvalidReferrer,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    // The accounts below are only required for referrals paid in an SPL quote mint
+    #[account(
+        address = referral.quote_mint @ LaunchPadErrorCode::InvalidQuoteMint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = referral,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub referral_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = referrer,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub referrer_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawReferralFees<'info> {
+    pub fn withdraw(&mut self) -> Result<()> {
+        let amount = self.referral.withdraw()?;
+
+        if self.referral.quote_mint == NATIVE_MINT {
+            // The accrued lamports sit on top of the referral's rent exemption
+            self.referral.sub_lamports(amount)?;
+            self.referrer.add_lamports(amount)?;
+        } else {
+            self.transfer_quote_to_referrer(amount)?;
+        }
+
+        emit!(ReferralFeesWithdrawn {
+            referrer: self.referrer.key(),
+            quote_mint: self.referral.quote_mint,
+            amount,
+            lifetime_fees: self.referral.lifetime_fees,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    fn transfer_quote_to_referrer(&self, amount: u64) -> Result<()> {
+        let (
+            Some(quote_mint),
+            Some(referral_quote_account),
+            Some(referrer_quote_account),
+            Some(quote_token_program),
+        ) = (
+            &self.quote_mint,
+            &self.referral_quote_account,
+            &self.referrer_quote_account,
+            &self.quote_token_program,
+        )
+        else {
+            return err!(LaunchPadErrorCode::MissingQuoteAccounts);
+        };
+
+        let signer: &[&[&[u8]]] = &[&[
+            Referral::SEED,
+            self.referrer.to_account_info().key.as_ref(),
+            self.referral.quote_mint.as_ref(),
+            &[self.referral.bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: referral_quote_account.to_account_info(),
+                    to: referrer_quote_account.to_account_info(),
+                    authority: self.referral.to_account_info(),
+                    mint: quote_mint.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+            quote_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawReferralFees>) -> Result<()> {
+    ctx.accounts.withdraw()?;
+    msg!("Referral fees withdrawn");
+    Ok(())
+}
@@ -2,35 +2,23 @@
 use anchor_lang::{prelude::*, system_program};
 
 use anchor_spl::token::spl_token::instruction::AuthorityType;
-use anchor_spl::token::{self, close_account, set_authority, CloseAccount, SetAuthority, Token};
+use anchor_spl::token::{self, set_authority, SetAuthority, Token};
 use anchor_spl::token_interface::Mint;
 
-use anchor_lang::solana_program::rent::{
-    DEFAULT_EXEMPTION_THRESHOLD, DEFAULT_LAMPORTS_PER_BYTE_YEAR,
-};
-use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_2022,
-    token_interface::{
-        token_metadata_initialize, Token2022, TokenAccount, TokenMetadataInitialize,
-    },
+    token_interface::{self, TokenAccount, TokenInterface},
 };
-use spl_token_metadata_interface::state::TokenMetadata;
-use spl_type_length_value::variable_len_pack::VariableLenPack;
 
 use crate::{
-    calc_token_amount_out, initial_virtual_asset_reserve, LaunchPadConfig, LaunchPadErrorCode,
-    LaunchPadToken, LaunchPadTokenGraduated, LaunchPadTokenStatus, ProtocolStatus,
-    MAX_TOKEN_NAME_LENGTH, MAX_TOKEN_SYMBOL_LENGTH, MAX_TOKEN_URI_LENGTH, MIN_TOKEN_NAME_LENGTH,
-    MIN_TOKEN_SYMBOL_LENGTH, MIN_TOKEN_URI_LENGTH, RAYDIUM_CPMM_ID, TOKEN_GRADUATION_AMOUNT,
-    TOKEN_TOTAL_SUPPLY,
+    LaunchPadConfig, LaunchPadErrorCode, LaunchPadToken, LaunchPadTokenGraduated,
+    LaunchPadTokenStatus, RAYDIUM_CPMM_ID,
 };
 
 use raydium_cpmm_cpi::{
     cpi,
     program::RaydiumCpmm,
-    states::{AmmConfig, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
+    states::{AmmConfig, OBSERVATION_SEED, POOL_LP_MINT_SEED},
 };
 
 #[derive(Accounts)]
@@ -51,7 +39,11 @@ pub struct GraduateToRaydium<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
-    #[account()]
+    // A Token-2022 mint, or a classic SPL Token mint for Metaplex metadata launches
+    #[account(
+        mut,
+        mint::token_program = mint_token_program,
+    )]
     pub mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
@@ -72,7 +64,7 @@ pub struct GraduateToRaydium<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = launch_pad_config,
-        associated_token::token_program = token_program_2022,
+        associated_token::token_program = mint_token_program,
     )]
     pub launch_pad_token_account: InterfaceAccount<'info, TokenAccount>,
 
@@ -107,10 +99,12 @@ pub struct GraduateToRaydium<'info> {
     )]
     pub pool_state: UncheckedAccount<'info>,
 
+    // wSOL for native launches, otherwise the SPL quote mint the token was priced in
     #[account(
-        mint::token_program = token_program,
+        address = launch_pad_token.quote_mint @ LaunchPadErrorCode::InvalidQuoteMint,
+        mint::token_program = quote_token_program,
     )]
-    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: pool lp mint, init by cp-swap
     #[account(
@@ -134,7 +128,7 @@ pub struct GraduateToRaydium<'info> {
         bump,
         token::mint = mint,
         token::authority = investor,
-        token::token_program = token_program_2022,
+        token::token_program = mint_token_program,
     )]
     pub vault_graduation_token_account: InterfaceAccount<'info, TokenAccount>,
 
@@ -146,12 +140,21 @@ pub struct GraduateToRaydium<'info> {
             launch_pad_token.key().as_ref(),
         ],
         bump,
-        token::mint = wsol_mint,
+        token::mint = quote_mint,
         token::authority = investor,
-        token::token_program = token_program,
+        token::token_program = quote_token_program,
     )]
     pub vault_asset_graduation_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    // The quote raised by an SPL quoted launch, not required for native launches
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = vault_graduation,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     /// CHECK: creator lp ATA token account, init by cp-swap
     #[account(mut)]
     pub lp_token: UncheckedAccount<'info>,
@@ -202,7 +205,8 @@ pub struct GraduateToRaydium<'info> {
     pub observation_state: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
-    pub token_program_2022: Program<'info, Token2022>,
+    pub mint_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -210,21 +214,17 @@ pub struct GraduateToRaydium<'info> {
 
 impl<'info> GraduateToRaydium<'info> {
     pub fn graduate(&mut self) -> Result<()> {
-        require!(
-            self.launch_pad_config.status == ProtocolStatus::Active,
-            LaunchPadErrorCode::ProtocolConfigNotActive
-        );
-        if self.launch_pad_token.status == LaunchPadTokenStatus::Graduated {
-            return Err(LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled.into());
-        }
-        if self.launch_pad_token.status != LaunchPadTokenStatus::ReadyToGraduate {
+        if !self
+            .launch_pad_token
+            .ready_to_graduate(&self.launch_pad_config)?
+        {
             return Ok(());
         }
-        require!(
-            self.launch_pad_token.status == LaunchPadTokenStatus::ReadyToGraduate,
-            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled
-        );
-        self.wrap_sol_to_graduation()?;
+        if self.launch_pad_token.is_native_quote() {
+            self.wrap_sol_to_graduation()?;
+        } else {
+            self.transfer_quote_to_graduation()?;
+        }
         self.transfer_tokens_to_graduation(self.launch_pad_config.bump)?;
         self.vault_asset_graduation_token_account.reload()?;
         self.vault_graduation_token_account.reload()?;
@@ -236,7 +236,7 @@ impl<'info> GraduateToRaydium<'info> {
         self.vault_graduation_token_account.reload()?;
         self.launch_pad_token_account.reload()?;
 
-        self.close_token_account_2022(
+        self.close_mint_token_account(
             &self.vault_graduation_token_account.to_account_info(),
             &self.investor.to_account_info(),
             &self.investor.to_account_info(),
@@ -250,7 +250,8 @@ impl<'info> GraduateToRaydium<'info> {
         )?;
         let launch_pad_config_bump = self.launch_pad_config.bump;
         let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
-        self.close_token_account_2022(
+        self.burn_unsold_tokens(signer)?;
+        self.close_mint_token_account(
             &self.launch_pad_token_account.to_account_info(),
             &self.launch_pad_config.to_account_info(),
             &self.vault.to_account_info(),
@@ -262,8 +263,8 @@ impl<'info> GraduateToRaydium<'info> {
             lp_mint: self.lp_mint.key(),
             lp_token: self.lp_token.key(),
             pool_state: self.pool_state.key(),
-            asset_amount: asset_amount,
-            token_amount: token_amount,
+            asset_amount,
+            token_amount,
             status: LaunchPadTokenStatus::Graduated,
             timestamp: Clock::get()?.unix_timestamp,
         });
@@ -274,21 +275,21 @@ impl<'info> GraduateToRaydium<'info> {
     fn create_pool(&self) -> Result<()> {
         let mut creator_token_0 = self.vault_asset_graduation_token_account.to_account_info();
         let mut creator_token_1 = self.vault_graduation_token_account.to_account_info();
-        let mut token_0_mint = self.wsol_mint.to_account_info();
+        let mut token_0_mint = self.quote_mint.to_account_info();
         let mut token_1_mint = self.mint.to_account_info();
-        let mut token_0_program = self.token_program.to_account_info();
-        let mut token_1_program = self.token_program_2022.to_account_info();
+        let mut token_0_program = self.quote_token_program.to_account_info();
+        let mut token_1_program = self.mint_token_program.to_account_info();
         let mut init_amount_0 = self.vault_asset_graduation_token_account.amount;
         let mut init_amount_1 = self.vault_graduation_token_account.amount;
 
         // Token_0 mint, the key must smaller then token_1 mint.
-        if self.wsol_mint.key() > self.mint.key() {
+        if self.quote_mint.key() > self.mint.key() {
             creator_token_0 = self.vault_graduation_token_account.to_account_info();
             creator_token_1 = self.vault_asset_graduation_token_account.to_account_info();
             token_0_mint = self.mint.to_account_info();
-            token_1_mint = self.wsol_mint.to_account_info();
-            token_0_program = self.token_program_2022.to_account_info();
-            token_1_program = self.token_program.to_account_info();
+            token_1_mint = self.quote_mint.to_account_info();
+            token_0_program = self.mint_token_program.to_account_info();
+            token_1_program = self.quote_token_program.to_account_info();
             init_amount_0 = self.vault_graduation_token_account.amount;
             init_amount_1 = self.vault_asset_graduation_token_account.amount;
         }
@@ -298,19 +299,19 @@ impl<'info> GraduateToRaydium<'info> {
             amm_config: self.amm_config.to_account_info(),
             authority: self.authority.to_account_info(),
             pool_state: self.pool_state.to_account_info(),
-            token_0_mint: token_0_mint,
-            token_1_mint: token_1_mint,
+            token_0_mint,
+            token_1_mint,
             lp_mint: self.lp_mint.to_account_info(),
-            creator_token_0: creator_token_0,
-            creator_token_1: creator_token_1,
+            creator_token_0,
+            creator_token_1,
             creator_lp_token: self.lp_token.to_account_info(),
             token_0_vault: self.token_0_vault.to_account_info(),
             token_1_vault: self.token_1_vault.to_account_info(),
             create_pool_fee: self.create_pool_fee.to_account_info(),
             observation_state: self.observation_state.to_account_info(),
             token_program: self.token_program.to_account_info(),
-            token_0_program: token_0_program,
-            token_1_program: token_1_program,
+            token_0_program,
+            token_1_program,
             associated_token_program: self.associated_token_program.to_account_info(),
             system_program: self.system_program.to_account_info(),
             rent: self.rent.to_account_info(),
@@ -377,13 +378,40 @@ impl<'info> GraduateToRaydium<'info> {
         Ok(())
     }
 
+    fn transfer_quote_to_graduation(&self) -> Result<()> {
+        let Some(vault_quote_account) = &self.vault_quote_account else {
+            return err!(LaunchPadErrorCode::MissingQuoteAccounts);
+        };
+        let launch_pad_vault_bump = self.launch_pad_token.vault_bump;
+        let signer: &[&[&[u8]]] = &[&[
+            LaunchPadToken::VAULT_SEED,
+            self.mint.to_account_info().key.as_ref(),
+            &[launch_pad_vault_bump],
+        ]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.quote_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: vault_quote_account.to_account_info(),
+                    to: self.vault_asset_graduation_token_account.to_account_info(),
+                    authority: self.vault_graduation.to_account_info(),
+                    mint: self.quote_mint.to_account_info(),
+                },
+                signer,
+            ),
+            vault_quote_account.amount,
+            self.quote_mint.decimals,
+        )?;
+        Ok(())
+    }
+
     fn transfer_tokens_to_graduation(&self, launch_pad_config_bump: u8) -> Result<()> {
         let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
-        let amount = self.launch_pad_token_account.amount;
-        token_2022::transfer_checked(
+        let amount = self.launch_pad_token.graduation_token_amount;
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                self.token_program_2022.to_account_info(),
-                token_2022::TransferChecked {
+                self.mint_token_program.to_account_info(),
+                token_interface::TransferChecked {
                     from: self.launch_pad_token_account.to_account_info(),
                     to: self.vault_graduation_token_account.to_account_info(),
                     authority: self.launch_pad_config.to_account_info(),
@@ -392,7 +420,29 @@ impl<'info> GraduateToRaydium<'info> {
                 signer,
             ),
             amount,
-            9,
+            self.mint.decimals,
+        )?;
+        Ok(())
+    }
+
+    /// Curve tokens left over the graduation allocation were never sold, burn them so the
+    /// launch pad token account can be closed.
+    fn burn_unsold_tokens(&self, signer: &[&[&[u8]]]) -> Result<()> {
+        let amount = self.launch_pad_token_account.amount;
+        if amount == 0 {
+            return Ok(());
+        }
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                self.mint_token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.launch_pad_token_account.to_account_info(),
+                    authority: self.launch_pad_config.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
         )?;
         Ok(())
     }
@@ -404,42 +454,42 @@ impl<'info> GraduateToRaydium<'info> {
         beneficiary: &AccountInfo<'info>,
         signer: &[&[&[u8]]],
     ) -> Result<()> {
-        let close_accounts = CloseAccount {
+        let close_accounts = token_interface::CloseAccount {
             account: account_to_close.to_account_info(),
             destination: beneficiary.to_account_info(),
             authority: owner.to_account_info(),
         };
 
         let close_cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            self.quote_token_program.to_account_info(),
             close_accounts,
             signer,
         );
 
-        close_account(close_cpi_ctx)?;
+        token_interface::close_account(close_cpi_ctx)?;
         Ok(())
     }
 
-    fn close_token_account_2022(
+    fn close_mint_token_account(
         &self,
         account_to_close: &AccountInfo<'info>,
         owner: &AccountInfo<'info>,
         beneficiary: &AccountInfo<'info>,
         signer: &[&[&[u8]]],
     ) -> Result<()> {
-        let close_accounts = token_2022::CloseAccount {
+        let close_accounts = token_interface::CloseAccount {
             account: account_to_close.to_account_info(),
             destination: beneficiary.to_account_info(),
             authority: owner.to_account_info(),
         };
 
         let close_cpi_ctx = CpiContext::new_with_signer(
-            self.token_program_2022.to_account_info(),
+            self.mint_token_program.to_account_info(),
             close_accounts,
             signer,
         );
 
-        token_2022::close_account(close_cpi_ctx)?;
+        token_interface::close_account(close_cpi_ctx)?;
         Ok(())
     }
 }
@@ -0,0 +1,42 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{migrate_account, LaunchPadToken};
+
+#[derive(Accounts)]
+pub struct MigrateLaunchPadToken<'info> {
+    // Anyone can migrate a launch pad token, the payer covers the larger account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: only used to derive the launch pad token address.
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: written by an earlier layout, read by `migrate_account` once it has been grown.
+    #[account(
+        mut,
+        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_pad_token: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateLaunchPadToken<'info> {
+    pub fn migrate(&mut self) -> Result<()> {
+        migrate_account(
+            &self.launch_pad_token.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            LaunchPadToken::DISCRIMINATOR.len() + LaunchPadToken::INIT_SPACE,
+            LaunchPadToken::migrate,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<MigrateLaunchPadToken>) -> Result<()> {
+    ctx.accounts.migrate()?;
+    msg!("Launch pad token migrated");
+    Ok(())
+}
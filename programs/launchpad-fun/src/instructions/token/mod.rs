@@ -1,2 +1,6 @@
 pub mod graduate_to_raydium;
+pub mod migrate_launch_pad_token;
+pub mod settle_auction;
 pub use graduate_to_raydium::*;
+pub use migrate_launch_pad_token::*;
+pub use settle_auction::*;
@@ -0,0 +1,162 @@
+#![allow(deprecated, unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{Auction, AuctionSettled, BuyLeg, LaunchPadConfig, LaunchPadToken, TradeQuote};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    // Anyone can settle once the reveal phase is over
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [LaunchPadConfig::SEED],
+        bump
+    )]
+    pub launch_pad_config: Account<'info, LaunchPadConfig>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadConfig::VAULT_SEED],
+        bump = launch_pad_config.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadToken::VAULT_SEED, mint.key().as_ref()],
+        bump = launch_pad_token.vault_bump,
+    )]
+    pub vault_graduation: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [LaunchPadToken::SEED, mint.key().as_ref()],
+        bump = launch_pad_token.bump,
+    )]
+    pub launch_pad_token: Account<'info, LaunchPadToken>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_pad_config,
+        associated_token::token_program = token_program,
+    )]
+    pub launch_pad_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [Auction::SEED, mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    // Holds the auctioned tokens until the bidders claim them
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub auction_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleAuction<'info> {
+    /// Fills every revealed bid with one curve buy so they all pay the same clearing price,
+    /// then opens the token for trading. The fill pays the base buy fee, the launch fee
+    /// schedule only starts once trading opens.
+    pub fn settle(&mut self, bumps: SettleAuctionBumps) -> Result<()> {
+        self.launch_pad_config.require_active()?;
+
+        let total_revealed = self.auction.total_revealed;
+        let quote = if total_revealed > 0 {
+            self.launch_pad_token.quote_buy_at_base_fee(
+                &self.launch_pad_config,
+                total_revealed,
+                false,
+            )?
+        } else {
+            TradeQuote::default()
+        };
+        self.auction.settle(quote.amount_in, quote.amount_out)?;
+
+        if quote.amount_in > 0 {
+            self.settle_buy(&quote, bumps.launch_pad_config)?;
+        }
+        self.launch_pad_token.end_auction()?;
+
+        emit!(AuctionSettled {
+            mint: self.mint.key(),
+            total_revealed,
+            asset_amount: quote.amount_in,
+            token_amount: quote.amount_out,
+            fee: quote.fee,
+            status: self.launch_pad_token.status,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pays the curve and fees out of the bid deposits like `buy_token` and sets the tokens
+    /// aside for the bidders.
+    fn settle_buy(&mut self, quote: &TradeQuote, launch_pad_config_bump: u8) -> Result<()> {
+        let split = quote.buy_split(&self.launch_pad_config, false)?;
+
+        self.auction.sub_lamports(quote.amount_in)?;
+        split.pay(|leg, amount| {
+            match leg {
+                BuyLeg::Curve => {
+                    self.vault_graduation.add_lamports(amount)?;
+                }
+                BuyLeg::Protocol => {
+                    self.vault.add_lamports(amount)?;
+                }
+                // The creator share is held on the launch pad token until claimed
+                BuyLeg::Creator => {
+                    self.launch_pad_token.add_lamports(amount)?;
+                }
+                // Auction bids are never referred
+                BuyLeg::Referral => {}
+            }
+            Ok(())
+        })?;
+
+        let signer: &[&[&[u8]]] = &[&[LaunchPadConfig::SEED, &[launch_pad_config_bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: self.launch_pad_token_account.to_account_info(),
+                    to: self.auction_token_account.to_account_info(),
+                    authority: self.launch_pad_config.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                },
+                signer,
+            ),
+            quote.amount_out,
+            self.mint.decimals,
+        )?;
+
+        self.launch_pad_token
+            .apply_buy(quote, &split, self.auction.key())
+    }
+}
+
+pub fn handler(ctx: Context<SettleAuction>) -> Result<()> {
+    ctx.accounts.settle(ctx.bumps)?;
+    msg!("Auction settled");
+    Ok(())
+}
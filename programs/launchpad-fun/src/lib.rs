@@ -1,19 +1,28 @@
 #![allow(deprecated, unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
+pub mod allowlist;
 pub mod constants;
 pub mod errors;
 pub mod events;
+pub mod fees;
 pub mod instructions;
 pub mod math;
+pub mod quotes;
 pub mod state;
 pub mod statuses;
 
+#[cfg(test)]
+mod test_utils;
+
+pub use allowlist::*;
 pub use constants::*;
 pub use errors::*;
 pub use events::*;
+pub use fees::*;
 pub use instructions::*;
 pub use math::*;
+pub use quotes::*;
 pub use state::*;
 pub use statuses::*;
 
@@ -31,21 +40,163 @@ pub mod launchpad_fun {
         Ok(())
     }
 
+    pub fn update_launch_pad_config(
+        ctx: Context<UpdateLaunchPadConfig>,
+        args: UpdateLaunchPadConfigArgs,
+    ) -> Result<()> {
+        update_launch_pad_config::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn migrate_launch_pad_config(ctx: Context<MigrateLaunchPadConfig>) -> Result<()> {
+        migrate_launch_pad_config::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        args: ProposeAuthorityArgs,
+    ) -> Result<()> {
+        propose_authority::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        accept_authority::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        cancel_authority_transfer::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn pause_protocol(ctx: Context<PauseProtocol>) -> Result<()> {
+        pause_protocol::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn unpause_protocol(ctx: Context<UnpauseProtocol>) -> Result<()> {
+        unpause_protocol::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn withdraw_protocol_fees(
+        ctx: Context<WithdrawProtocolFees>,
+        args: WithdrawProtocolFeesArgs,
+    ) -> Result<()> {
+        withdraw_protocol_fees::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn withdraw_protocol_quote_fees(
+        ctx: Context<WithdrawProtocolQuoteFees>,
+        args: WithdrawProtocolQuoteFeesArgs,
+    ) -> Result<()> {
+        withdraw_protocol_quote_fees::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn add_quote_mint(ctx: Context<AddQuoteMint>) -> Result<()> {
+        add_quote_mint::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn remove_quote_mint(
+        ctx: Context<RemoveQuoteMint>,
+        args: RemoveQuoteMintArgs,
+    ) -> Result<()> {
+        remove_quote_mint::handler(ctx, args)?;
+        Ok(())
+    }
+
     pub fn create_token(ctx: Context<CreateToken>, args: CreateTokenArgs) -> Result<()> {
         create_token::handler(ctx, args)?;
         Ok(())
     }
 
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        claim_creator_fees::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        claim_vested::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        args: UpdateTokenMetadataArgs,
+    ) -> Result<()> {
+        update_token_metadata::handler(ctx, args)?;
+        Ok(())
+    }
+
     pub fn buy_token(ctx: Context<BuyToken>, args: BuyTokenArgs) -> Result<()> {
         buy_token::handler(ctx, args)?;
         Ok(())
     }
 
+    pub fn buy_token_exact_out(ctx: Context<BuyToken>, args: BuyTokenExactOutArgs) -> Result<()> {
+        buy_token::exact_out_handler(ctx, args)?;
+        Ok(())
+    }
+
     pub fn sell_token(ctx: Context<SellToken>, args: SellTokenArgs) -> Result<()> {
         sell_token::handler(ctx, args)?;
         Ok(())
     }
 
+    pub fn quote_buy(ctx: Context<QuoteBuy>, args: QuoteBuyArgs) -> Result<TradeQuote> {
+        quote_buy::handler(ctx, args)
+    }
+
+    pub fn quote_sell(ctx: Context<QuoteSell>, args: QuoteSellArgs) -> Result<TradeQuote> {
+        quote_sell::handler(ctx, args)
+    }
+
+    pub fn commit_auction_bid(
+        ctx: Context<CommitAuctionBid>,
+        args: CommitAuctionBidArgs,
+    ) -> Result<()> {
+        commit_auction_bid::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn reveal_auction_bid(
+        ctx: Context<RevealAuctionBid>,
+        args: RevealAuctionBidArgs,
+    ) -> Result<()> {
+        reveal_auction_bid::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        settle_auction::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn claim_auction_bid(ctx: Context<ClaimAuctionBid>) -> Result<()> {
+        claim_auction_bid::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn init_referral(ctx: Context<InitReferral>, args: InitReferralArgs) -> Result<()> {
+        init_referral::handler(ctx, args)?;
+        Ok(())
+    }
+
+    pub fn withdraw_referral_fees(ctx: Context<WithdrawReferralFees>) -> Result<()> {
+        withdraw_referral_fees::handler(ctx)?;
+        Ok(())
+    }
+
+    pub fn migrate_launch_pad_token(ctx: Context<MigrateLaunchPadToken>) -> Result<()> {
+        migrate_launch_pad_token::handler(ctx)?;
+        Ok(())
+    }
+
     pub fn graduate_to_raydium(ctx: Context<GraduateToRaydium>) -> Result<()> {
         graduate_to_raydium::handler(ctx)?;
         Ok(())
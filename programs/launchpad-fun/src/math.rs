@@ -1,10 +1,23 @@
-use crate::{LaunchPadErrorCode, K, TOKEN_TOTAL_SUPPLY};
+use anchor_lang::prelude::{borsh, AnchorDeserialize, AnchorSerialize, InitSpace};
 
-pub fn initial_virtual_asset_reserve(asset_rate: u64) -> u128 {
+use crate::{
+    LaunchPadErrorCode, CURVE_CONSTANT_PRODUCT, CURVE_EXPONENTIAL, CURVE_LINEAR, CURVE_SIGMOID, K,
+    PRICE_SCALE, WAD,
+};
+
+/// Virtual asset reserve a constant product curve over `token_supply` base units starts with.
+pub fn initial_virtual_asset_reserve(
+    asset_rate: u64,
+    token_supply: u128,
+) -> Result<u128, LaunchPadErrorCode> {
     let k = (K * 10000) / asset_rate;
     let a = (k as u128) * 10000 * (1e9 as u128);
-    let b = a / TOKEN_TOTAL_SUPPLY;
-    (b * (1e9 as u128)) / 10000 as u128
+    let b = a
+        .checked_div(token_supply)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    b.checked_mul(1e9 as u128)
+        .map(|reserve| reserve / 10_000)
+        .ok_or(LaunchPadErrorCode::MathOverflow)
 }
 
 pub fn calc_token_amount_out(
@@ -45,21 +58,625 @@ pub fn calc_asset_amount_out(
     Ok(asset_amount_out as u64)
 }
 
+pub fn calc_asset_amount_in(
+    token_amount_out: u64,
+    current_k: u128,
+    virtual_asset_reserve: u128,
+    virtual_token_reserve: u128,
+) -> Result<u64, LaunchPadErrorCode> {
+    let token_amount_out = token_amount_out as u128;
+    let amount = virtual_token_reserve
+        .checked_sub(token_amount_out)
+        .filter(|amount| *amount > 0)
+        .ok_or(LaunchPadErrorCode::InsufficientTokenLiquidity)?;
+    // Round the new asset reserve up so the pool never gives away more than k allows.
+    let a = current_k
+        .checked_add(amount - 1)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?
+        .checked_div(amount)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    let asset_amount_in = a
+        .checked_sub(virtual_asset_reserve)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    u64::try_from(asset_amount_in).map_err(|_| LaunchPadErrorCode::MathOverflow)
+}
+
+/// The bonding curve a token trades on. Prices are quoted in lamports per
+/// `PRICE_SCALE` token base units and rates are `WAD` fixed point.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub enum CurveType {
+    /// x * y = k over virtual reserves
+    #[default]
+    ConstantProduct,
+    /// price = initial_price + slope * sold
+    Linear { initial_price: u64, slope: u64 },
+    /// price = initial_price * e^(growth_rate * sold)
+    Exponential {
+        initial_price: u64,
+        growth_rate: u64,
+    },
+    /// price = max_price / (1 + e^(-steepness * (sold - midpoint)))
+    Sigmoid {
+        max_price: u64,
+        steepness: u64,
+        midpoint: u64,
+    },
+}
+
+/// Where a token currently sits on its curve.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CurveReserves {
+    /// Tokens the curve started with
+    pub token_supply: u128,
+    pub virtual_token_reserve: u128,
+    pub virtual_asset_reserve: u128,
+    /// x * y of the constant product curve, 0 and unused for the other curves
+    pub current_k: u128,
+}
+
+impl CurveReserves {
+    pub fn sold(&self) -> Result<u128, LaunchPadErrorCode> {
+        self.token_supply
+            .checked_sub(self.virtual_token_reserve)
+            .ok_or(LaunchPadErrorCode::MathOverflow)
+    }
+}
+
+pub trait BondingCurve {
+    /// Tokens received for `asset_amount_in` lamports.
+    fn token_amount_out(
+        &self,
+        asset_amount_in: u64,
+        reserves: &CurveReserves,
+    ) -> Result<u64, LaunchPadErrorCode>;
+
+    /// Lamports received for selling `token_amount_in` tokens back to the curve.
+    fn asset_amount_out(
+        &self,
+        token_amount_in: u64,
+        reserves: &CurveReserves,
+    ) -> Result<u64, LaunchPadErrorCode>;
+
+    /// Lamports needed to buy exactly `token_amount_out` tokens, rounded in the curve's favour.
+    fn asset_amount_in(
+        &self,
+        token_amount_out: u64,
+        reserves: &CurveReserves,
+    ) -> Result<u64, LaunchPadErrorCode>;
+
+    /// Marginal price in lamports per `PRICE_SCALE` token base units.
+    fn spot_price(&self, reserves: &CurveReserves) -> Result<u128, LaunchPadErrorCode>;
+}
+
+impl CurveType {
+    /// Bit of this curve in `LaunchPadConfig::allowed_curves`.
+    pub fn mask(&self) -> u8 {
+        match self {
+            CurveType::ConstantProduct => CURVE_CONSTANT_PRODUCT,
+            CurveType::Linear { .. } => CURVE_LINEAR,
+            CurveType::Exponential { .. } => CURVE_EXPONENTIAL,
+            CurveType::Sigmoid { .. } => CURVE_SIGMOID,
+        }
+    }
+
+    /// Checks the parameters and that the whole `token_supply` can be priced.
+    pub fn validate(&self, token_supply: u128) -> Result<(), LaunchPadErrorCode> {
+        let valid = match *self {
+            CurveType::ConstantProduct => true,
+            CurveType::Linear { initial_price, .. } => initial_price > 0,
+            CurveType::Exponential {
+                initial_price,
+                growth_rate,
+            } => initial_price > 0 && growth_rate > 0,
+            CurveType::Sigmoid {
+                max_price,
+                steepness,
+                ..
+            } => max_price > 0 && steepness > 0,
+        };
+        if !valid || self.cost(token_supply).is_err() {
+            return Err(LaunchPadErrorCode::InvalidCurveParams);
+        }
+        Ok(())
+    }
+
+    /// Virtual asset reserve a new token starts with. Only the constant
+    /// product curve is seeded, the other curves start from zero, so their
+    /// `current_k` stays 0 and they are priced from the tokens sold alone.
+    pub fn initial_virtual_asset_reserve(
+        &self,
+        asset_rate: u64,
+        token_supply: u128,
+    ) -> Result<u128, LaunchPadErrorCode> {
+        match self {
+            CurveType::ConstantProduct => initial_virtual_asset_reserve(asset_rate, token_supply),
+            _ => Ok(0),
+        }
+    }
+
+    /// Lamports needed to buy the first `sold` tokens off the curve, rounded down.
+    fn cost(&self, sold: u128) -> Result<u128, LaunchPadErrorCode> {
+        self.cost_rounded(sold, mul_div)
+    }
+
+    /// Same as `cost` but rounded up, for what buyers pay.
+    fn cost_up(&self, sold: u128) -> Result<u128, LaunchPadErrorCode> {
+        self.cost_rounded(sold, mul_div_up)
+    }
+
+    fn cost_rounded(
+        &self,
+        sold: u128,
+        div: fn(u128, u128, u128) -> Result<u128, LaunchPadErrorCode>,
+    ) -> Result<u128, LaunchPadErrorCode> {
+        match *self {
+            CurveType::ConstantProduct => Ok(0),
+            CurveType::Linear {
+                initial_price,
+                slope,
+            } => {
+                let base = div(initial_price as u128, sold, PRICE_SCALE)?;
+                let price_increase = div(slope as u128, sold, PRICE_SCALE)?;
+                let ramp = div(price_increase, sold, 2 * PRICE_SCALE)?;
+                base.checked_add(ramp)
+                    .ok_or(LaunchPadErrorCode::MathOverflow)
+            }
+            CurveType::Exponential {
+                initial_price,
+                growth_rate,
+            } => {
+                let exponent = div(growth_rate as u128, sold, PRICE_SCALE)?;
+                let growth = exp_wad(exponent)? - WAD;
+                div(initial_price as u128, growth, growth_rate as u128)
+            }
+            CurveType::Sigmoid {
+                max_price,
+                steepness,
+                midpoint,
+            } => {
+                let area = softplus_wad(sigmoid_exponent(steepness, midpoint, sold)?)?
+                    .saturating_sub(softplus_wad(sigmoid_exponent(steepness, midpoint, 0)?)?);
+                div(max_price as u128, area, steepness as u128)
+            }
+        }
+    }
+
+    /// Tokens sold once the curve has been paid `cost` lamports in total, the inverse of
+    /// `cost` up to the fixed point error.
+    fn inverse_cost(&self, cost: u128) -> Result<u128, LaunchPadErrorCode> {
+        match *self {
+            CurveType::ConstantProduct => Err(LaunchPadErrorCode::InvalidCurveParams),
+            CurveType::Linear {
+                initial_price,
+                slope,
+            } => {
+                // sold = 2 * cost / (initial_price + sqrt(initial_price^2 + 2 * slope * cost)),
+                // the quadratic formula without the cancellation of a small slope
+                let initial_price = initial_price as u128;
+                let discriminant = (slope as u128)
+                    .checked_mul(2)
+                    .and_then(|value| value.checked_mul(cost))
+                    .and_then(|value| value.checked_add(initial_price * initial_price))
+                    .ok_or(LaunchPadErrorCode::MathOverflow)?;
+                mul_div(
+                    cost.checked_mul(2)
+                        .ok_or(LaunchPadErrorCode::MathOverflow)?,
+                    PRICE_SCALE,
+                    initial_price + sqrt(discriminant),
+                )
+            }
+            CurveType::Exponential {
+                initial_price,
+                growth_rate,
+            } => {
+                let growth = mul_div(cost, growth_rate as u128, initial_price as u128)?;
+                let exponent = ln_wad(
+                    WAD.checked_add(growth)
+                        .ok_or(LaunchPadErrorCode::MathOverflow)?,
+                )?;
+                mul_div(exponent, PRICE_SCALE, growth_rate as u128)
+            }
+            CurveType::Sigmoid {
+                max_price,
+                steepness,
+                midpoint,
+            } => {
+                let area = mul_div(cost, steepness as u128, max_price as u128)?;
+                let softplus = softplus_wad(sigmoid_exponent(steepness, midpoint, 0)?)?
+                    .checked_add(area)
+                    .ok_or(LaunchPadErrorCode::MathOverflow)?;
+                let exponent = softplus_inverse_wad(softplus)?;
+                let distance = mul_div(exponent.unsigned_abs(), PRICE_SCALE, steepness as u128)?;
+                if exponent >= 0 {
+                    (midpoint as u128)
+                        .checked_add(distance)
+                        .ok_or(LaunchPadErrorCode::MathOverflow)
+                } else {
+                    Ok((midpoint as u128).saturating_sub(distance))
+                }
+            }
+        }
+    }
+
+    fn price(&self, sold: u128) -> Result<u128, LaunchPadErrorCode> {
+        match *self {
+            CurveType::ConstantProduct => Ok(0),
+            CurveType::Linear {
+                initial_price,
+                slope,
+            } => (initial_price as u128)
+                .checked_add(mul_div(slope as u128, sold, PRICE_SCALE)?)
+                .ok_or(LaunchPadErrorCode::MathOverflow),
+            CurveType::Exponential {
+                initial_price,
+                growth_rate,
+            } => {
+                let exponent = mul_div(growth_rate as u128, sold, PRICE_SCALE)?;
+                mul_div(initial_price as u128, exp_wad(exponent)?, WAD)
+            }
+            CurveType::Sigmoid {
+                max_price,
+                steepness,
+                midpoint,
+            } => {
+                let exponent = sigmoid_exponent(steepness, midpoint, sold)?;
+                let decay = if exponent >= 0 {
+                    exp_neg_wad(exponent.unsigned_abs())?
+                } else if exponent.unsigned_abs() > EXP_NEG_CUTOFF {
+                    return Ok(0);
+                } else {
+                    exp_wad(exponent.unsigned_abs())?
+                };
+                mul_div(max_price as u128, WAD, WAD + decay)
+            }
+        }
+    }
+
+    /// Lamports a buyer pays to take the curve from `from` to `to` sold, rounded up.
+    fn buy_cost(&self, from: u128, to: u128) -> Result<u128, LaunchPadErrorCode> {
+        self.cost_up(to)?
+            .checked_sub(self.cost(from)?)
+            .ok_or(LaunchPadErrorCode::MathOverflow)
+    }
+
+    /// Lamports a seller receives for taking the curve from `to` back to `from` sold, rounded down.
+    fn sell_proceeds(&self, from: u128, to: u128) -> Result<u128, LaunchPadErrorCode> {
+        self.cost(to)?
+            .checked_sub(self.cost(from)?)
+            .ok_or(LaunchPadErrorCode::MathOverflow)
+    }
+}
+
+impl BondingCurve for CurveType {
+    fn token_amount_out(
+        &self,
+        asset_amount_in: u64,
+        reserves: &CurveReserves,
+    ) -> Result<u64, LaunchPadErrorCode> {
+        if *self == CurveType::ConstantProduct {
+            return calc_token_amount_out(
+                asset_amount_in,
+                reserves.current_k,
+                reserves.virtual_asset_reserve,
+                reserves.virtual_token_reserve,
+            );
+        }
+
+        // Largest amount whose cost fits the budget, so buys always round in the curve's favour.
+        // Inverting the cost brackets the amount between what the budget buys with and without
+        // the rounding, and a fixed number of bisection steps narrows it down from there so the
+        // compute cost of a buy stays bounded.
+        let sold = reserves.sold()?;
+        let spot_cost = self.cost(sold)?;
+        let reserve = reserves.virtual_token_reserve;
+        let affordable = |amount: u128| {
+            amount == 0
+                || self
+                    .cost_up(sold + amount)
+                    .ok()
+                    .and_then(|cost| cost.checked_sub(spot_cost))
+                    .is_some_and(|cost| cost <= asset_amount_in as u128)
+        };
+        let estimate = |budget: u128| {
+            spot_cost
+                .checked_add(budget)
+                .and_then(|cost| self.inverse_cost(cost).ok())
+                .map_or(reserve, |sold_after| sold_after.saturating_sub(sold))
+                .min(reserve)
+        };
+        let margin = |amount: u128| (amount >> ESTIMATE_MARGIN_SHIFT) + MIN_ESTIMATE_MARGIN;
+        let budget = asset_amount_in as u128;
+
+        // Keep `low` affordable and `high` out of reach.
+        let amount = estimate(budget);
+        let mut high = amount.saturating_add(margin(amount)).min(reserve + 1);
+        let mut low = if affordable(amount) {
+            amount
+        } else {
+            let amount = estimate(budget.saturating_sub(COST_ROUNDING_SLACK));
+            let amount = amount.saturating_sub(margin(amount));
+            if affordable(amount) {
+                amount
+            } else {
+                0
+            }
+        };
+        if high <= reserve && affordable(high) {
+            (low, high) = (high, reserve + 1);
+        }
+        for _ in 0..MAX_REFINE_STEPS {
+            if high - low <= 1 {
+                break;
+            }
+            let mid = low + (high - low) / 2;
+            if affordable(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        u64::try_from(low).map_err(|_| LaunchPadErrorCode::MathOverflow)
+    }
+
+    fn asset_amount_out(
+        &self,
+        token_amount_in: u64,
+        reserves: &CurveReserves,
+    ) -> Result<u64, LaunchPadErrorCode> {
+        if *self == CurveType::ConstantProduct {
+            return calc_asset_amount_out(
+                token_amount_in,
+                reserves.current_k,
+                reserves.virtual_token_reserve,
+                reserves.virtual_asset_reserve,
+            );
+        }
+
+        let sold = reserves.sold()?;
+        let sold_after = sold
+            .checked_sub(token_amount_in as u128)
+            .ok_or(LaunchPadErrorCode::InsufficientAssetLiquidity)?;
+        let asset_amount_out = self.sell_proceeds(sold_after, sold)?;
+        u64::try_from(asset_amount_out).map_err(|_| LaunchPadErrorCode::MathOverflow)
+    }
+
+    fn asset_amount_in(
+        &self,
+        token_amount_out: u64,
+        reserves: &CurveReserves,
+    ) -> Result<u64, LaunchPadErrorCode> {
+        if *self == CurveType::ConstantProduct {
+            return calc_asset_amount_in(
+                token_amount_out,
+                reserves.current_k,
+                reserves.virtual_asset_reserve,
+                reserves.virtual_token_reserve,
+            );
+        }
+
+        require_token_liquidity(token_amount_out, reserves)?;
+        let sold = reserves.sold()?;
+        let asset_amount_in = self.buy_cost(sold, sold + token_amount_out as u128)?;
+        u64::try_from(asset_amount_in).map_err(|_| LaunchPadErrorCode::MathOverflow)
+    }
+
+    fn spot_price(&self, reserves: &CurveReserves) -> Result<u128, LaunchPadErrorCode> {
+        if *self == CurveType::ConstantProduct {
+            return mul_div(
+                reserves.virtual_asset_reserve,
+                PRICE_SCALE,
+                reserves.virtual_token_reserve,
+            );
+        }
+        self.price(reserves.sold()?)
+    }
+}
+
+fn require_token_liquidity(
+    token_amount_out: u64,
+    reserves: &CurveReserves,
+) -> Result<(), LaunchPadErrorCode> {
+    if token_amount_out as u128 > reserves.virtual_token_reserve {
+        return Err(LaunchPadErrorCode::InsufficientTokenLiquidity);
+    }
+    Ok(())
+}
+
+/// Above this exponent e^-x is below one WAD unit.
+const EXP_NEG_CUTOFF: u128 = 42 * WAD;
+/// exp_wad evaluates its series on x / 2^EXP_HALVINGS and squares back up.
+const EXP_HALVINGS: u32 = 8;
+
+/// ln(2) as a `WAD` fixed point.
+const LN_2_WAD: u128 = 693_147_180_559_945_309;
+/// The estimate from `CurveType::inverse_cost` is searched within estimate / 2^ESTIMATE_MARGIN_SHIFT
+/// plus MIN_ESTIMATE_MARGIN base units either side.
+const ESTIMATE_MARGIN_SHIFT: u32 = 40;
+const MIN_ESTIMATE_MARGIN: u128 = 1 << 16;
+/// Lamports the rounding in `CurveType::buy_cost` can add over the exact cost.
+const COST_ROUNDING_SLACK: u128 = 3;
+/// Bisection steps `token_amount_out` spends narrowing the estimate down.
+const MAX_REFINE_STEPS: u32 = 12;
+
+/// floor(a * b / denominator) without overflowing on the intermediate product
+/// as long as the result fits.
+fn mul_div(a: u128, b: u128, denominator: u128) -> Result<u128, LaunchPadErrorCode> {
+    if denominator == 0 {
+        return Err(LaunchPadErrorCode::MathOverflow);
+    }
+    if let Some(product) = a.checked_mul(b) {
+        return Ok(product / denominator);
+    }
+    // Split a into whole and fractional multiples of the denominator.
+    let (a_high, a_low) = (a / denominator, a % denominator);
+    let (b_high, b_low) = (b / denominator, b % denominator);
+    let low = a_low
+        .checked_mul(b_low)
+        .map(|product| product / denominator)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?;
+    a_high
+        .checked_mul(b)
+        .and_then(|value| value.checked_add(a_low.checked_mul(b_high)?))
+        .and_then(|value| value.checked_add(low))
+        .ok_or(LaunchPadErrorCode::MathOverflow)
+}
+
+/// ceil(a * b / denominator), see `mul_div`.
+fn mul_div_up(a: u128, b: u128, denominator: u128) -> Result<u128, LaunchPadErrorCode> {
+    let quotient = mul_div(a, b, denominator)?;
+    let remainder = (a % denominator)
+        .checked_mul(b % denominator)
+        .ok_or(LaunchPadErrorCode::MathOverflow)?
+        % denominator;
+    if remainder == 0 {
+        return Ok(quotient);
+    }
+    quotient
+        .checked_add(1)
+        .ok_or(LaunchPadErrorCode::MathOverflow)
+}
+
+/// e^x for a `WAD` fixed point x.
+fn exp_wad(x: u128) -> Result<u128, LaunchPadErrorCode> {
+    let reduced = x >> EXP_HALVINGS;
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut i: u128 = 1;
+    loop {
+        term = mul_div(term, reduced, i * WAD)?;
+        if term == 0 {
+            break;
+        }
+        sum = sum
+            .checked_add(term)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        i += 1;
+    }
+    for _ in 0..EXP_HALVINGS {
+        sum = mul_div(sum, sum, WAD)?;
+    }
+    Ok(sum)
+}
+
+/// e^-x for a `WAD` fixed point x.
+pub(crate) fn exp_neg_wad(x: u128) -> Result<u128, LaunchPadErrorCode> {
+    if x > EXP_NEG_CUTOFF {
+        return Ok(0);
+    }
+    Ok(WAD * WAD / exp_wad(x)?)
+}
+
+/// ln(1 + y) for a `WAD` fixed point y in [0, 1], using ln(1 + y) = 2 * atanh(y / (2 + y)).
+fn ln_1p_wad(y: u128) -> Result<u128, LaunchPadErrorCode> {
+    let z = mul_div(y, WAD, 2 * WAD + y)?;
+    let z_squared = mul_div(z, z, WAD)?;
+    let mut term = z;
+    let mut sum = z;
+    let mut i: u128 = 3;
+    loop {
+        term = mul_div(term, z_squared, WAD)?;
+        if term == 0 {
+            break;
+        }
+        sum += term / i;
+        i += 2;
+    }
+    Ok(2 * sum)
+}
+
+/// ln(x) for a `WAD` fixed point x >= 1, reduced to ln(1 + y) with y in [0, 1).
+fn ln_wad(x: u128) -> Result<u128, LaunchPadErrorCode> {
+    if x < WAD {
+        return Err(LaunchPadErrorCode::MathOverflow);
+    }
+    let halvings = (x / WAD).ilog2();
+    let reduced = x >> halvings;
+    Ok(halvings as u128 * LN_2_WAD + ln_1p_wad(reduced - WAD)?)
+}
+
+/// floor(sqrt(x)) by Newton's method.
+fn sqrt(x: u128) -> u128 {
+    if x < 2 {
+        return x;
+    }
+    let mut root = 1u128 << (x.ilog2() / 2 + 1);
+    loop {
+        let next = (root + x / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// ln(1 + e^z) for a signed `WAD` fixed point z.
+fn softplus_wad(z: i128) -> Result<u128, LaunchPadErrorCode> {
+    let tail = ln_1p_wad(exp_neg_wad(z.unsigned_abs())?)?;
+    if z > 0 {
+        (z as u128)
+            .checked_add(tail)
+            .ok_or(LaunchPadErrorCode::MathOverflow)
+    } else {
+        Ok(tail)
+    }
+}
+
+/// z with ln(1 + e^z) = y for a `WAD` fixed point y > 0, z = y + ln(1 - e^-y).
+fn softplus_inverse_wad(y: u128) -> Result<i128, LaunchPadErrorCode> {
+    let remainder = WAD - exp_neg_wad(y)?;
+    if remainder == 0 {
+        return Err(LaunchPadErrorCode::MathOverflow);
+    }
+    let correction = ln_wad(mul_div(WAD, WAD, remainder)?)?;
+    let y = i128::try_from(y).map_err(|_| LaunchPadErrorCode::MathOverflow)?;
+    Ok(y - correction as i128)
+}
+
+/// steepness * (sold - midpoint) as a signed `WAD` fixed point.
+fn sigmoid_exponent(steepness: u64, midpoint: u64, sold: u128) -> Result<i128, LaunchPadErrorCode> {
+    let midpoint = midpoint as u128;
+    let distance = mul_div(steepness as u128, sold.abs_diff(midpoint), PRICE_SCALE)?;
+    let distance = i128::try_from(distance).map_err(|_| LaunchPadErrorCode::MathOverflow)?;
+    Ok(if sold >= midpoint {
+        distance
+    } else {
+        -distance
+    })
+}
+
 #[cfg(test)]
 mod test {
-    use crate::TOKEN_TOTAL_SUPPLY;
+    use proptest::prelude::*;
+
+    use crate::{TOKEN_GRADUATION_AMOUNT, TOKEN_TOTAL_SUPPLY};
 
     use super::*;
 
+    const MAX_SOLD: u128 = TOKEN_TOTAL_SUPPLY - TOKEN_GRADUATION_AMOUNT;
+
     #[test]
     fn test_initial_virtual_asset_reserve() {
         // 4.285.714,2857
-        assert_eq!(initial_virtual_asset_reserve(7), 4_285_714_285_700_000);
+        assert_eq!(
+            initial_virtual_asset_reserve(7, TOKEN_TOTAL_SUPPLY).unwrap(),
+            4_285_714_285_700_000
+        );
+        // 100M tokens with 6 decimals
+        assert_eq!(
+            initial_virtual_asset_reserve(7, 100_000_000_000_000).unwrap(),
+            42_857_142_857_142_800_000
+        );
+        assert!(matches!(
+            initial_virtual_asset_reserve(7, 0),
+            Err(LaunchPadErrorCode::MathOverflow)
+        ));
     }
 
     #[test]
     fn test_calc_token_amount_out() {
-        let current_asset_supply = initial_virtual_asset_reserve(7);
+        let current_asset_supply = initial_virtual_asset_reserve(7, TOKEN_TOTAL_SUPPLY).unwrap();
         let current_k = current_asset_supply * TOKEN_TOTAL_SUPPLY;
         assert_eq!(current_k, 4285714285700000000000000000000000);
         let result = calc_token_amount_out(
@@ -71,9 +688,63 @@ mod test {
         assert_eq!(result.unwrap(), 230_999_946_640); // 230,99994664
     }
 
+    #[test]
+    fn test_calc_asset_amount_in() {
+        let current_asset_supply = initial_virtual_asset_reserve(7, TOKEN_TOTAL_SUPPLY).unwrap();
+        let current_k = current_asset_supply * TOKEN_TOTAL_SUPPLY;
+        let token_amount_out = 230_999_946_640; // 230,99994664
+        let result = calc_asset_amount_in(
+            token_amount_out,
+            current_k,
+            current_asset_supply,
+            TOKEN_TOTAL_SUPPLY,
+        )
+        .unwrap();
+        // 0,99 plus one lamport of rounding in the pool's favour
+        assert_eq!(result, 990_000_001);
+    }
+
+    #[test]
+    fn test_calc_asset_amount_in_rounds_in_pool_favour() {
+        let current_asset_supply = 100_000_000_000;
+        let current_k = current_asset_supply * TOKEN_TOTAL_SUPPLY;
+        for token_amount_out in [1, 1_000, 9_851_972_869_944_057, 400_000_000_000_000_000] {
+            let asset_amount_in = calc_asset_amount_in(
+                token_amount_out,
+                current_k,
+                current_asset_supply,
+                TOKEN_TOTAL_SUPPLY,
+            )
+            .unwrap();
+            let bought = calc_token_amount_out(
+                asset_amount_in,
+                current_k,
+                current_asset_supply,
+                TOKEN_TOTAL_SUPPLY,
+            )
+            .unwrap();
+            assert!(bought >= token_amount_out);
+        }
+    }
+
+    #[test]
+    fn test_calc_asset_amount_in_whole_reserve() {
+        let current_asset_supply = initial_virtual_asset_reserve(7, TOKEN_TOTAL_SUPPLY).unwrap();
+        let current_k = current_asset_supply * TOKEN_TOTAL_SUPPLY;
+        assert!(matches!(
+            calc_asset_amount_in(
+                TOKEN_TOTAL_SUPPLY as u64,
+                current_k,
+                current_asset_supply,
+                TOKEN_TOTAL_SUPPLY,
+            ),
+            Err(LaunchPadErrorCode::InsufficientTokenLiquidity)
+        ));
+    }
+
     #[test]
     fn test_calc_asset_amount_out() {
-        let current_asset_supply = initial_virtual_asset_reserve(7);
+        let current_asset_supply = initial_virtual_asset_reserve(7, TOKEN_TOTAL_SUPPLY).unwrap();
         let current_k = current_asset_supply * TOKEN_TOTAL_SUPPLY;
         assert_eq!(current_k, 4285714285700000000000000000000000);
         let result = calc_asset_amount_out(
@@ -84,4 +755,346 @@ mod test {
         );
         assert_eq!(result.unwrap(), 989_999_543); // 0,989999543
     }
+
+    fn reserves_at(curve: &CurveType, initial_asset_reserve: u128, sold: u128) -> CurveReserves {
+        let virtual_token_reserve = TOKEN_TOTAL_SUPPLY - sold;
+        let current_k = initial_asset_reserve * TOKEN_TOTAL_SUPPLY;
+        let virtual_asset_reserve = match curve {
+            CurveType::ConstantProduct => current_k / virtual_token_reserve,
+            _ => curve.cost(sold).unwrap(),
+        };
+        CurveReserves {
+            token_supply: TOKEN_TOTAL_SUPPLY,
+            virtual_token_reserve,
+            virtual_asset_reserve,
+            current_k,
+        }
+    }
+
+    fn check_price_monotonic(
+        curve: &CurveType,
+        initial_asset_reserve: u128,
+        sold: u128,
+        step: u128,
+    ) -> std::result::Result<(), TestCaseError> {
+        let later = (sold + step).min(MAX_SOLD);
+        let price = curve
+            .spot_price(&reserves_at(curve, initial_asset_reserve, sold))
+            .unwrap();
+        let later_price = curve
+            .spot_price(&reserves_at(curve, initial_asset_reserve, later))
+            .unwrap();
+        prop_assert!(later_price >= price);
+        Ok(())
+    }
+
+    fn check_round_trip_not_profitable(
+        curve: &CurveType,
+        initial_asset_reserve: u128,
+        sold: u128,
+        asset_amount_in: u64,
+    ) -> std::result::Result<(), TestCaseError> {
+        let reserves = reserves_at(curve, initial_asset_reserve, sold);
+        let token_amount_out = curve.token_amount_out(asset_amount_in, &reserves).unwrap();
+        let after_buy = CurveReserves {
+            virtual_token_reserve: reserves.virtual_token_reserve - token_amount_out as u128,
+            virtual_asset_reserve: reserves.virtual_asset_reserve + asset_amount_in as u128,
+            ..reserves
+        };
+        let asset_amount_out = curve
+            .asset_amount_out(token_amount_out, &after_buy)
+            .unwrap();
+        prop_assert!(asset_amount_out <= asset_amount_in);
+        Ok(())
+    }
+
+    fn linear_curve() -> impl Strategy<Value = CurveType> {
+        (1_000_000_000u64..1_000_000_000_000, 0u64..1_000_000_000_000).prop_map(
+            |(initial_price, slope)| CurveType::Linear {
+                initial_price,
+                slope,
+            },
+        )
+    }
+
+    fn exponential_curve() -> impl Strategy<Value = CurveType> {
+        (
+            1_000_000_000u64..100_000_000_000,
+            100_000_000_000_000_000u64..5_000_000_000_000_000_000,
+        )
+            .prop_map(|(initial_price, growth_rate)| CurveType::Exponential {
+                initial_price,
+                growth_rate,
+            })
+    }
+
+    fn sigmoid_curve() -> impl Strategy<Value = CurveType> {
+        (
+            10_000_000_000u64..1_000_000_000_000,
+            1_000_000_000_000_000_000u64..10_000_000_000_000_000_000,
+            0u64..1_000_000_000_000_000_000,
+        )
+            .prop_map(|(max_price, steepness, midpoint)| CurveType::Sigmoid {
+                max_price,
+                steepness,
+                midpoint,
+            })
+    }
+
+    /// Largest affordable amount by exhaustive binary search over the whole reserve.
+    fn search_token_amount_out(curve: &CurveType, asset_amount_in: u64, sold: u128) -> u128 {
+        let spot_cost = curve.cost(sold).unwrap();
+        let (mut low, mut high) = (0u128, TOKEN_TOTAL_SUPPLY - sold);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            let affordable = curve
+                .cost_up(sold + mid)
+                .ok()
+                .and_then(|cost| cost.checked_sub(spot_cost))
+                .is_some_and(|cost| cost <= asset_amount_in as u128);
+            if affordable {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    fn check_token_amount_out_near_optimal(
+        curve: &CurveType,
+        sold: u128,
+        asset_amount_in: u64,
+    ) -> std::result::Result<(), TestCaseError> {
+        let token_amount_out = curve
+            .token_amount_out(asset_amount_in, &reserves_at(curve, 0, sold))
+            .unwrap() as u128;
+        let optimal = search_token_amount_out(curve, asset_amount_in, sold);
+        prop_assert!(
+            token_amount_out == 0
+                || curve.buy_cost(sold, sold + token_amount_out).unwrap()
+                    <= asset_amount_in as u128
+        );
+        // What the buyer leaves on the table is worth less than a couple of lamports.
+        let left_over =
+            curve.cost(sold + optimal).unwrap() - curve.cost(sold + token_amount_out).unwrap();
+        prop_assert!(left_over <= 2, "left over {}", left_over);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn constant_product_price_is_monotonic(
+            initial_asset_reserve in 1_000_000_000u128..1_000_000_000_000,
+            sold in 0..MAX_SOLD,
+            step in 0..MAX_SOLD,
+        ) {
+            check_price_monotonic(&CurveType::ConstantProduct, initial_asset_reserve, sold, step)?;
+        }
+
+        #[test]
+        fn constant_product_round_trip_is_not_profitable(
+            initial_asset_reserve in 1_000_000_000u128..1_000_000_000_000,
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_round_trip_not_profitable(
+                &CurveType::ConstantProduct,
+                initial_asset_reserve,
+                sold,
+                asset_amount_in,
+            )?;
+        }
+
+        #[test]
+        fn linear_price_is_monotonic(curve in linear_curve(), sold in 0..MAX_SOLD, step in 0..MAX_SOLD) {
+            check_price_monotonic(&curve, 0, sold, step)?;
+        }
+
+        #[test]
+        fn linear_round_trip_is_not_profitable(
+            curve in linear_curve(),
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_round_trip_not_profitable(&curve, 0, sold, asset_amount_in)?;
+        }
+
+        #[test]
+        fn exponential_price_is_monotonic(
+            curve in exponential_curve(),
+            sold in 0..MAX_SOLD,
+            step in 0..MAX_SOLD,
+        ) {
+            check_price_monotonic(&curve, 0, sold, step)?;
+        }
+
+        #[test]
+        fn exponential_round_trip_is_not_profitable(
+            curve in exponential_curve(),
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_round_trip_not_profitable(&curve, 0, sold, asset_amount_in)?;
+        }
+
+        #[test]
+        fn sigmoid_price_is_monotonic(curve in sigmoid_curve(), sold in 0..MAX_SOLD, step in 0..MAX_SOLD) {
+            check_price_monotonic(&curve, 0, sold, step)?;
+        }
+
+        #[test]
+        fn linear_token_amount_out_is_near_optimal(
+            curve in linear_curve(),
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_token_amount_out_near_optimal(&curve, sold, asset_amount_in)?;
+        }
+
+        #[test]
+        fn exponential_token_amount_out_is_near_optimal(
+            curve in exponential_curve(),
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_token_amount_out_near_optimal(&curve, sold, asset_amount_in)?;
+        }
+
+        #[test]
+        fn sigmoid_token_amount_out_is_near_optimal(
+            curve in sigmoid_curve(),
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_token_amount_out_near_optimal(&curve, sold, asset_amount_in)?;
+        }
+
+        #[test]
+        fn sigmoid_round_trip_is_not_profitable(
+            curve in sigmoid_curve(),
+            sold in 0..MAX_SOLD,
+            asset_amount_in in 1u64..100_000_000_000,
+        ) {
+            check_round_trip_not_profitable(&curve, 0, sold, asset_amount_in)?;
+        }
+    }
+
+    #[test]
+    fn test_constant_product_curve_matches_reserve_math() {
+        let curve = CurveType::ConstantProduct;
+        let reserves = reserves_at(&curve, 100_000_000_000, 0);
+        assert_eq!(
+            curve.token_amount_out(995_000_000, &reserves).unwrap(),
+            9_851_972_869_944_057
+        );
+        assert_eq!(
+            curve.token_amount_out(995_000_000, &reserves).unwrap(),
+            calc_token_amount_out(
+                995_000_000,
+                reserves.current_k,
+                reserves.virtual_asset_reserve,
+                reserves.virtual_token_reserve,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_flat_linear_curve() {
+        // 100 lamports per whole token
+        let curve = CurveType::Linear {
+            initial_price: 100_000_000_000,
+            slope: 0,
+        };
+        let reserves = reserves_at(&curve, 0, 0);
+        // Base units worth less than a lamport are not given away.
+        assert_eq!(
+            curve.token_amount_out(1_000_000_000, &reserves).unwrap(),
+            10_000_000_000_000_000
+        );
+        assert_eq!(
+            curve
+                .asset_amount_in(10_000_000_000_000_000, &reserves)
+                .unwrap(),
+            1_000_000_000
+        );
+        // A fraction of a lamport is charged as a whole one.
+        assert_eq!(curve.asset_amount_in(1, &reserves).unwrap(), 1);
+        assert_eq!(
+            curve
+                .asset_amount_in(10_000_000_000_000_001, &reserves)
+                .unwrap(),
+            1_000_000_001
+        );
+    }
+
+    #[test]
+    fn test_mul_div_up() {
+        assert_eq!(mul_div_up(10, 10, 5).unwrap(), 20);
+        assert_eq!(mul_div_up(10, 10, 3).unwrap(), 34);
+        assert_eq!(mul_div_up(0, 10, 3).unwrap(), 0);
+        assert_eq!(mul_div_up(u128::MAX, 3, 6).unwrap(), u128::MAX / 2 + 1);
+        assert!(matches!(
+            mul_div_up(1, 1, 0),
+            Err(LaunchPadErrorCode::MathOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_exp_and_ln_approximations() {
+        assert_eq!(exp_wad(0).unwrap(), WAD);
+        // e = 2.718281828459045235
+        assert!(exp_wad(WAD).unwrap().abs_diff(2_718_281_828_459_045_235) < 10_000);
+        // ln(2) = 0.693147180559945309
+        assert!(ln_1p_wad(WAD).unwrap().abs_diff(693_147_180_559_945_309) < 1_000);
+        assert_eq!(softplus_wad(0).unwrap(), ln_1p_wad(WAD).unwrap());
+        // ln(1000) = 6.907755278982137052
+        assert!(
+            ln_wad(1_000 * WAD)
+                .unwrap()
+                .abs_diff(6_907_755_278_982_137_052)
+                < 10_000
+        );
+        assert_eq!(sqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(sqrt(99), 9);
+    }
+
+    #[test]
+    fn test_validate_curve_params() {
+        assert!(CurveType::ConstantProduct
+            .validate(TOKEN_TOTAL_SUPPLY)
+            .is_ok());
+        for curve in [
+            CurveType::Linear {
+                initial_price: 0,
+                slope: 1,
+            },
+            CurveType::Exponential {
+                initial_price: 1,
+                growth_rate: 0,
+            },
+            CurveType::Sigmoid {
+                max_price: 1,
+                steepness: 0,
+                midpoint: 0,
+            },
+        ] {
+            assert!(matches!(
+                curve.validate(TOKEN_TOTAL_SUPPLY),
+                Err(LaunchPadErrorCode::InvalidCurveParams)
+            ));
+        }
+
+        // e^184 over ten times the supply cannot be priced
+        let steep = CurveType::Exponential {
+            initial_price: 1,
+            growth_rate: u64::MAX,
+        };
+        assert!(steep.validate(TOKEN_TOTAL_SUPPLY).is_ok());
+        assert!(matches!(
+            steep.validate(TOKEN_TOTAL_SUPPLY * 10),
+            Err(LaunchPadErrorCode::InvalidCurveParams)
+        ));
+    }
 }
@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+
+use crate::{LaunchPadConfig, LaunchPadErrorCode};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TradeQuote {
+    /// Amount paid by the trader, lamports for buys and tokens for sells
+    pub amount_in: u64,
+    /// Amount received by the trader, tokens for buys and lamports for sells
+    pub amount_out: u64,
+    /// Protocol fee charged on the trade in lamports
+    pub fee: u64,
+    /// Share of the fee accrued to the token creator
+    pub creator_fee: u64,
+    /// Part of the fee charged above the base buy fee by the launch fee schedule
+    pub launch_fee: u64,
+    /// Virtual reserve of the token after the trade
+    pub virtual_token_amount: u64,
+    /// Virtual reserve of the asset after the trade
+    pub virtual_asset_amount: u64,
+    /// Virtual reserve amount for graduation after the trade
+    pub virtual_graduation_amount: u64,
+    /// Whether the trade moves the token to ReadyToGraduate
+    pub ready_to_graduate: bool,
+}
+
+impl TradeQuote {
+    /// Splits a buy between the curve and the fee recipients. Only the base part of the
+    /// protocol fee is shared with the referrer of `referred` buys.
+    pub fn buy_split(&self, config: &LaunchPadConfig, referred: bool) -> Result<BuySplit> {
+        let asset_amount = self
+            .amount_in
+            .checked_sub(self.fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let protocol_fee = self
+            .fee
+            .checked_sub(self.creator_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let referral_fee = if referred {
+            config.calculate_referral_fee(
+                protocol_fee
+                    .checked_sub(self.launch_fee)
+                    .ok_or(LaunchPadErrorCode::MathOverflow)?,
+            )?
+        } else {
+            0
+        };
+        Ok(BuySplit {
+            asset_amount,
+            protocol_fee: protocol_fee
+                .checked_sub(referral_fee)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?,
+            creator_fee: self.creator_fee,
+            referral_fee,
+        })
+    }
+
+    /// Splits the quote asset released by a sell between the trader and the fee recipients.
+    /// The referrer of `referred` sells shares the protocol part of the fee.
+    pub fn sell_split(&self, config: &LaunchPadConfig, referred: bool) -> Result<SellSplit> {
+        let protocol_fee = self
+            .fee
+            .checked_sub(self.creator_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let referral_fee = if referred {
+            config.calculate_referral_fee(protocol_fee)?
+        } else {
+            0
+        };
+        Ok(SellSplit {
+            asset_amount: self.amount_out,
+            protocol_fee: protocol_fee
+                .checked_sub(referral_fee)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?,
+            creator_fee: self.creator_fee,
+            referral_fee,
+        })
+    }
+}
+
+/// Where the quote asset paid for a buy goes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BuyLeg {
+    /// The graduation vault backing the curve
+    Curve,
+    /// The protocol fee vault
+    Protocol,
+    /// The launch pad token, holding the creator share until claimed
+    Creator,
+    /// The referral PDA of the trader's referrer
+    Referral,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct BuySplit {
+    /// Quote asset added to the curve
+    pub asset_amount: u64,
+    /// Protocol fee left after the creator and referral shares
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+}
+
+impl BuySplit {
+    /// Calls `pay` for every leg that receives a non-zero amount.
+    pub fn pay(&self, mut pay: impl FnMut(BuyLeg, u64) -> Result<()>) -> Result<()> {
+        for (leg, amount) in [
+            (BuyLeg::Curve, self.asset_amount),
+            (BuyLeg::Protocol, self.protocol_fee),
+            (BuyLeg::Creator, self.creator_fee),
+            (BuyLeg::Referral, self.referral_fee),
+        ] {
+            if amount > 0 {
+                pay(leg, amount)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the quote asset released by a sell goes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SellLeg {
+    /// The trader selling the tokens
+    Trader,
+    /// The protocol fee vault
+    Protocol,
+    /// The launch pad token, holding the creator share until claimed
+    Creator,
+    /// The referral PDA of the trader's referrer
+    Referral,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SellSplit {
+    /// Quote asset paid out to the trader
+    pub asset_amount: u64,
+    /// Protocol fee left after the creator and referral shares
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+}
+
+impl SellSplit {
+    /// Calls `pay` for every leg that receives a non-zero amount.
+    pub fn pay(&self, mut pay: impl FnMut(SellLeg, u64) -> Result<()>) -> Result<()> {
+        for (leg, amount) in [
+            (SellLeg::Trader, self.asset_amount),
+            (SellLeg::Protocol, self.protocol_fee),
+            (SellLeg::Creator, self.creator_fee),
+            (SellLeg::Referral, self.referral_fee),
+        ] {
+            if amount > 0 {
+                pay(leg, amount)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buy_split() {
+        let config = LaunchPadConfig {
+            referral_fee_bps: 100_000,
+            ..Default::default()
+        };
+        let quote = TradeQuote {
+            amount_in: 1_000_000_000,
+            fee: 60_000_000,
+            creator_fee: 5_000_000,
+            launch_fee: 50_000_000,
+            ..Default::default()
+        };
+
+        let split = quote.buy_split(&config, false).unwrap();
+        assert_eq!(
+            split,
+            BuySplit {
+                asset_amount: 940_000_000,
+                protocol_fee: 55_000_000,
+                creator_fee: 5_000_000,
+                referral_fee: 0,
+            }
+        );
+
+        // The referrer only shares the base fee, not the launch fee on top of it.
+        let split = quote.buy_split(&config, true).unwrap();
+        assert_eq!(split.referral_fee, 500_000);
+        assert_eq!(split.protocol_fee, 54_500_000);
+
+        let mut paid = vec![];
+        split
+            .pay(|leg, amount| {
+                paid.push((leg, amount));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            paid,
+            [
+                (BuyLeg::Curve, 940_000_000),
+                (BuyLeg::Protocol, 54_500_000),
+                (BuyLeg::Creator, 5_000_000),
+                (BuyLeg::Referral, 500_000),
+            ]
+        );
+        assert_eq!(
+            paid.iter().map(|(_, amount)| amount).sum::<u64>(),
+            quote.amount_in
+        );
+    }
+
+    #[test]
+    fn test_sell_split() {
+        let config = LaunchPadConfig {
+            referral_fee_bps: 100_000,
+            ..Default::default()
+        };
+        let quote = TradeQuote {
+            amount_in: 1_000_000,
+            amount_out: 940_000_000,
+            fee: 60_000_000,
+            creator_fee: 5_000_000,
+            ..Default::default()
+        };
+
+        let split = quote.sell_split(&config, false).unwrap();
+        assert_eq!(
+            split,
+            SellSplit {
+                asset_amount: 940_000_000,
+                protocol_fee: 55_000_000,
+                creator_fee: 5_000_000,
+                referral_fee: 0,
+            }
+        );
+
+        let split = quote.sell_split(&config, true).unwrap();
+        assert_eq!(split.referral_fee, 5_500_000);
+        assert_eq!(split.protocol_fee, 49_500_000);
+
+        let mut paid = vec![];
+        split
+            .pay(|leg, amount| {
+                paid.push((leg, amount));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            paid,
+            [
+                (SellLeg::Trader, 940_000_000),
+                (SellLeg::Protocol, 49_500_000),
+                (SellLeg::Creator, 5_000_000),
+                (SellLeg::Referral, 5_500_000),
+            ]
+        );
+        assert_eq!(
+            paid.iter().map(|(_, amount)| amount).sum::<u64>(),
+            quote.amount_out + quote.fee
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::{account, prelude::Pubkey, InitSpace};
+
+use crate::{LaunchPadErrorCode, DISC_AUCTION_ACCOUNT};
+
+/// Commit and reveal deadlines of a batch auction launch.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct AuctionSchedule {
+    /// Unix timestamp at which the commit phase ends and the reveal phase begins
+    pub commit_ends_at: i64,
+    /// Unix timestamp at which the reveal phase ends and the auction can be settled
+    pub reveal_ends_at: i64,
+}
+
+/// Sealed-bid batch auction opening a launch. Holds the bid deposits until they are claimed.
+#[derive(Default, Debug, InitSpace)]
+#[account(discriminator = DISC_AUCTION_ACCOUNT)]
+pub struct Auction {
+    /// The mint of the launch pad token being auctioned
+    pub mint: Pubkey,
+    /// The timestamp when the commit phase ends
+    pub commit_ends_at: i64,
+    /// The timestamp when the reveal phase ends
+    pub reveal_ends_at: i64,
+    /// Lamports deposited by every committed bid
+    pub total_deposits: u64,
+    /// Lamports bid by every revealed bid
+    pub total_revealed: u64,
+    /// Lamports spent on the curve at settlement, fees included
+    pub asset_filled: u64,
+    /// Tokens bought on the curve at settlement
+    pub token_filled: u64,
+    /// Whether the auction has been settled
+    pub settled: bool,
+    /// The bump seed for the PDA
+    pub bump: u8,
+}
+
+impl Auction {
+    pub const SEED: &'static [u8] = b"auction:";
+
+    pub fn init(&mut self, mint: Pubkey, schedule: AuctionSchedule, bump: u8) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < schedule.commit_ends_at
+                && schedule.commit_ends_at < schedule.reveal_ends_at,
+            LaunchPadErrorCode::InvalidAuctionSchedule
+        );
+        self.mint = mint;
+        self.commit_ends_at = schedule.commit_ends_at;
+        self.reveal_ends_at = schedule.reveal_ends_at;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn commit(&mut self, deposit: u64) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.commit_ends_at,
+            LaunchPadErrorCode::AuctionNotInCommitPhase
+        );
+        require!(deposit > 0, LaunchPadErrorCode::InvalidAuctionDeposit);
+        self.total_deposits = self
+            .total_deposits
+            .checked_add(deposit)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn reveal(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= self.commit_ends_at && now < self.reveal_ends_at,
+            LaunchPadErrorCode::AuctionNotInRevealPhase
+        );
+        self.total_revealed = self
+            .total_revealed
+            .checked_add(amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records the aggregate curve buy that fills every revealed bid.
+    pub fn settle(&mut self, asset_filled: u64, token_filled: u64) -> Result<()> {
+        require!(!self.settled, LaunchPadErrorCode::AuctionAlreadySettled);
+        require!(
+            Clock::get()?.unix_timestamp >= self.reveal_ends_at,
+            LaunchPadErrorCode::AuctionNotEnded
+        );
+        self.asset_filled = asset_filled;
+        self.token_filled = token_filled;
+        self.settled = true;
+        Ok(())
+    }
+
+    /// Returns the tokens and lamports refund owed for a bid of `revealed_amount` out of a
+    /// `deposit`. Every bid pays the same clearing price, tokens round down and lamports spent
+    /// round up so the claims never exceed what the auction holds.
+    pub fn fill(&self, revealed_amount: u64, deposit: u64) -> Result<(u64, u64)> {
+        require!(self.settled, LaunchPadErrorCode::AuctionNotSettled);
+        if revealed_amount == 0 {
+            return Ok((0, deposit));
+        }
+        let total_revealed = self.total_revealed as u128;
+        let token_amount = (revealed_amount as u128)
+            .checked_mul(self.token_filled as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?
+            / total_revealed;
+        let spent = (revealed_amount as u128)
+            .checked_mul(self.asset_filled as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?
+            .div_ceil(total_revealed);
+        let refund = (deposit as u128)
+            .checked_sub(spent)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok((token_amount as u64, refund as u64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_utils::warp_to;
+
+    use super::*;
+
+    fn auction() -> Auction {
+        warp_to(1_700_000_000);
+        let mut auction = Auction::default();
+        auction
+            .init(
+                Pubkey::new_unique(),
+                AuctionSchedule {
+                    commit_ends_at: 1_700_000_600,
+                    reveal_ends_at: 1_700_001_200,
+                },
+                255,
+            )
+            .unwrap();
+        auction
+    }
+
+    #[test]
+    fn test_auction_phases() {
+        let mut auction = auction();
+
+        auction.commit(3_000).unwrap();
+        auction.commit(1_000).unwrap();
+        assert_eq!(
+            auction.reveal(2_000).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotInRevealPhase.into()
+        );
+
+        warp_to(1_700_000_600);
+        assert_eq!(
+            auction.commit(1_000).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotInCommitPhase.into()
+        );
+        auction.reveal(2_000).unwrap();
+        assert_eq!(
+            auction.settle(1_000, 10).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotEnded.into()
+        );
+
+        warp_to(1_700_001_200);
+        assert_eq!(
+            auction.reveal(1_000).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotInRevealPhase.into()
+        );
+        auction.settle(1_000, 10).unwrap();
+        assert_eq!(
+            auction.settle(1_000, 10).unwrap_err(),
+            LaunchPadErrorCode::AuctionAlreadySettled.into()
+        );
+    }
+
+    #[test]
+    fn test_fill_at_uniform_price() {
+        let mut auction = auction();
+        auction.total_revealed = 3_000;
+        assert_eq!(
+            auction.fill(1_000, 1_500).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotSettled.into()
+        );
+
+        // the curve only fills 2_000 of the 3_000 lamports revealed
+        auction.settled = true;
+        auction.asset_filled = 2_000;
+        auction.token_filled = 1_000_000;
+
+        assert_eq!(auction.fill(1_000, 1_500).unwrap(), (333_333, 833));
+        assert_eq!(auction.fill(2_000, 2_000).unwrap(), (666_666, 666));
+        // unrevealed bids are refunded in full
+        assert_eq!(auction.fill(0, 4_000).unwrap(), (0, 4_000));
+    }
+}
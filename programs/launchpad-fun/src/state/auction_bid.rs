@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{account, prelude::Pubkey, InitSpace};
+
+use crate::{LaunchPadErrorCode, DISC_AUCTION_BID_ACCOUNT};
+
+/// Hashes a bid for the commit phase, for off-chain bid generation.
+pub fn auction_bid_commitment(bidder: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[bidder.as_ref(), &amount.to_le_bytes(), salt]).to_bytes()
+}
+
+#[derive(Default, Debug, InitSpace)]
+#[account(discriminator = DISC_AUCTION_BID_ACCOUNT)]
+pub struct AuctionBid {
+    /// The mint of the launch pad token being auctioned
+    pub mint: Pubkey,
+    /// The wallet that placed the bid
+    pub bidder: Pubkey,
+    /// Hash of the sealed bid, see `auction_bid_commitment`
+    pub commitment: [u8; 32],
+    /// Lamports deposited with the commitment, at least the bid to keep it hidden
+    pub deposit: u64,
+    /// Lamports bid once revealed, zero until then
+    pub revealed_amount: u64,
+    /// Whether the bid has been revealed
+    pub revealed: bool,
+    /// The bump seed for the PDA
+    pub bump: u8,
+}
+
+impl AuctionBid {
+    pub const SEED: &'static [u8] = b"auction_bid:";
+
+    pub fn commit(
+        &mut self,
+        mint: Pubkey,
+        bidder: Pubkey,
+        commitment: [u8; 32],
+        deposit: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.mint = mint;
+        self.bidder = bidder;
+        self.commitment = commitment;
+        self.deposit = deposit;
+        self.bump = bump;
+        Ok(())
+    }
+
+    /// Opens the sealed bid, the bid can't spend more than was deposited.
+    pub fn reveal(&mut self, amount: u64, salt: &[u8; 32]) -> Result<()> {
+        require!(
+            !self.revealed,
+            LaunchPadErrorCode::AuctionBidAlreadyRevealed
+        );
+        require!(
+            auction_bid_commitment(&self.bidder, amount, salt) == self.commitment
+                && amount <= self.deposit,
+            LaunchPadErrorCode::InvalidAuctionReveal
+        );
+        self.revealed_amount = amount;
+        self.revealed = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reveal_checks_commitment() {
+        let bidder = Pubkey::new_unique();
+        let salt = [7; 32];
+        let mut bid = AuctionBid::default();
+        bid.commit(
+            Pubkey::new_unique(),
+            bidder,
+            auction_bid_commitment(&bidder, 1_000, &salt),
+            1_500,
+            254,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bid.reveal(1_001, &salt).unwrap_err(),
+            LaunchPadErrorCode::InvalidAuctionReveal.into()
+        );
+        assert_eq!(
+            bid.reveal(1_000, &[8; 32]).unwrap_err(),
+            LaunchPadErrorCode::InvalidAuctionReveal.into()
+        );
+        bid.reveal(1_000, &salt).unwrap();
+        assert_eq!(bid.revealed_amount, 1_000);
+        assert_eq!(
+            bid.reveal(1_000, &salt).unwrap_err(),
+            LaunchPadErrorCode::AuctionBidAlreadyRevealed.into()
+        );
+    }
+
+    #[test]
+    fn test_reveal_cannot_exceed_deposit() {
+        let bidder = Pubkey::new_unique();
+        let salt = [7; 32];
+        let mut bid = AuctionBid::default();
+        bid.commit(
+            Pubkey::new_unique(),
+            bidder,
+            auction_bid_commitment(&bidder, 2_000, &salt),
+            1_500,
+            254,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bid.reveal(2_000, &salt).unwrap_err(),
+            LaunchPadErrorCode::InvalidAuctionReveal.into()
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::{account, prelude::Pubkey, InitSpace};
+
+use crate::{LaunchPadErrorCode, DISC_BUYER_RECORD_ACCOUNT};
+
+#[derive(Default, Debug, InitSpace)]
+#[account(discriminator = DISC_BUYER_RECORD_ACCOUNT)]
+pub struct BuyerRecord {
+    /// The mint of the launch pad token bought
+    pub mint: Pubkey,
+    /// The wallet that bought the token
+    pub buyer: Pubkey,
+    /// Cumulative tokens bought by the wallet on the curve
+    pub bought_amount: u64,
+    /// Tokens bought against the wallet's allowlist allocation
+    pub allowlist_claimed: u64,
+    /// The bump seed for the PDA
+    pub bump: u8,
+}
+
+impl BuyerRecord {
+    pub const SEED: &'static [u8] = b"buyer_record:";
+
+    /// Adds a buy to the wallet's total, rejecting it if the total would pass `max_amount`.
+    pub fn record_buy(
+        &mut self,
+        mint: Pubkey,
+        buyer: Pubkey,
+        bump: u8,
+        token_amount: u64,
+        max_amount: Option<u64>,
+    ) -> Result<()> {
+        if self.buyer == Pubkey::default() {
+            self.mint = mint;
+            self.buyer = buyer;
+            self.bump = bump;
+        }
+        let bought_amount = self
+            .bought_amount
+            .checked_add(token_amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        if let Some(max_amount) = max_amount {
+            require!(
+                bought_amount <= max_amount,
+                LaunchPadErrorCode::MaxWalletBuyExceeded
+            );
+        }
+        self.bought_amount = bought_amount;
+        Ok(())
+    }
+
+    /// Counts a presale buy against the wallet's allowlist allocation.
+    pub fn claim_allowlist(&mut self, token_amount: u64, allocation: u64) -> Result<()> {
+        let allowlist_claimed = self
+            .allowlist_claimed
+            .checked_add(token_amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        require!(
+            allowlist_claimed <= allocation,
+            LaunchPadErrorCode::AllowlistAllocationExceeded
+        );
+        self.allowlist_claimed = allowlist_claimed;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_buy_enforces_cap() {
+        let mint = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let mut record = BuyerRecord::default();
+
+        record
+            .record_buy(mint, buyer, 254, 600, Some(1_000))
+            .unwrap();
+        assert_eq!(record.buyer, buyer);
+        assert_eq!(record.bump, 254);
+
+        assert_eq!(
+            record
+                .record_buy(mint, buyer, 254, 401, Some(1_000))
+                .unwrap_err(),
+            LaunchPadErrorCode::MaxWalletBuyExceeded.into()
+        );
+        assert_eq!(record.bought_amount, 600);
+
+        record
+            .record_buy(mint, buyer, 254, 400, Some(1_000))
+            .unwrap();
+        // the cap lifts once the window is over
+        record.record_buy(mint, buyer, 254, 5_000, None).unwrap();
+        assert_eq!(record.bought_amount, 6_000);
+    }
+
+    #[test]
+    fn test_claim_allowlist_enforces_allocation() {
+        let mut record = BuyerRecord::default();
+
+        record.claim_allowlist(700, 1_000).unwrap();
+        assert_eq!(
+            record.claim_allowlist(301, 1_000).unwrap_err(),
+            LaunchPadErrorCode::AllowlistAllocationExceeded.into()
+        );
+        record.claim_allowlist(300, 1_000).unwrap();
+        assert_eq!(record.allowlist_claimed, 1_000);
+    }
+}
@@ -1,9 +1,40 @@
 pub const MIN_PROTOCOL_FEE: u32 = 5_000; //  0.5%
 pub const MAX_PROTOCOL_FEE: u32 = 10_000; // 1%
+pub const MAX_CREATOR_FEE_BPS: u32 = 1_000_000; // the whole trade fee
+pub const MAX_REFERRAL_FEE_BPS: u32 = 1_000_000; // the whole protocol fee
+pub const MAX_LAUNCH_FEE: u32 = 900_000; // 90%
 
 pub const MIN_ASSET_RATE: u64 = 1; // 1
 pub const MIN_GRADUATE_THRESHOLD: u64 = 1; // 1 lamport
 pub const MIN_CREATOR_SELL_DELAY: u64 = 3_600; // 1 hour
 
+pub const DEFAULT_MIN_TOKEN_SUPPLY: u64 = 1_000_000; // 1 million whole tokens
+pub const DEFAULT_MAX_TOKEN_SUPPLY: u64 = 10_000_000_000; // 10 billion whole tokens
+pub const MAX_TOKEN_DECIMALS: u8 = 9;
+pub const DEFAULT_MIN_GRADUATION_ALLOCATION: u32 = 100_000; // 10%
+pub const DEFAULT_MAX_GRADUATION_ALLOCATION: u32 = 500_000; // 50%
+pub const MAX_GRADUATION_ALLOCATION: u32 = 1_000_000; // 100%
+
+pub const MAX_QUOTE_MINTS: usize = 8;
+
+pub const MAX_METADATA_KEYS: usize = 8;
+pub const MAX_METADATA_KEY_LENGTH: usize = 32;
+pub const MAX_METADATA_VALUE_LENGTH: u16 = 256;
+
+pub const MAX_WALLET_SHARE: u32 = 1_000_000; // 100%
+pub const MAX_VESTING_SHARE: u32 = 200_000; // 20% of the curve supply
+
+pub const CURVE_CONSTANT_PRODUCT: u8 = 1 << 0;
+pub const CURVE_LINEAR: u8 = 1 << 1;
+pub const CURVE_EXPONENTIAL: u8 = 1 << 2;
+pub const CURVE_SIGMOID: u8 = 1 << 3;
+pub const ALL_CURVES: u8 =
+    CURVE_CONSTANT_PRODUCT | CURVE_LINEAR | CURVE_EXPONENTIAL | CURVE_SIGMOID;
+
 pub const DISC_LAUNCH_PAD_CONFIG_ACCOUNT: &[u8] = &[1];
 pub const DISC_LAUNCH_PAD_TOKEN_ACCOUNT: &[u8] = &[2];
+pub const DISC_REFERRAL_ACCOUNT: &[u8] = &[3];
+pub const DISC_BUYER_RECORD_ACCOUNT: &[u8] = &[4];
+pub const DISC_AUCTION_ACCOUNT: &[u8] = &[5];
+pub const DISC_AUCTION_BID_ACCOUNT: &[u8] = &[6];
+pub const DISC_VESTING_ACCOUNT: &[u8] = &[7];
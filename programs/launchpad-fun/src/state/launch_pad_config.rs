@@ -3,11 +3,110 @@ use anchor_lang::prelude::*;
 use anchor_lang::{account, prelude::Pubkey, InitSpace};
 
 use crate::{
-    LaunchPadConfigInitialized, LaunchPadErrorCode, LaunchPadPaused, LaunchPadUnpaused,
-    ProtocolStatus, DISC_LAUNCH_PAD_CONFIG_ACCOUNT, MAX_PROTOCOL_FEE, MIN_ASSET_RATE,
-    MIN_CREATOR_SELL_DELAY, MIN_GRADUATE_THRESHOLD, MIN_PROTOCOL_FEE,
+    calculate_amount_with_fee, calculate_fee, CurveType, LaunchFeeSchedule,
+    LaunchPadAuthorityAccepted, LaunchPadAuthorityProposed, LaunchPadAuthorityTransferCancelled,
+    LaunchPadConfigInitialized, LaunchPadConfigUpdated, LaunchPadErrorCode, LaunchPadPaused,
+    LaunchPadQuoteMintAdded, LaunchPadQuoteMintRemoved, LaunchPadUnpaused, ProtocolStatus,
+    UpdateLaunchPadConfigArgs, ALL_CURVES, CURVE_CONSTANT_PRODUCT,
+    DEFAULT_MAX_GRADUATION_ALLOCATION, DEFAULT_MAX_TOKEN_SUPPLY, DEFAULT_MIN_GRADUATION_ALLOCATION,
+    DEFAULT_MIN_TOKEN_SUPPLY, DISC_LAUNCH_PAD_CONFIG_ACCOUNT, MAX_CREATOR_FEE_BPS,
+    MAX_GRADUATION_ALLOCATION, MAX_METADATA_KEYS, MAX_METADATA_KEY_LENGTH,
+    MAX_METADATA_VALUE_LENGTH, MAX_PROTOCOL_FEE, MAX_QUOTE_MINTS, MAX_REFERRAL_FEE_BPS,
+    MAX_TOKEN_DECIMALS, MAX_VESTING_SHARE, MAX_WALLET_SHARE, MIN_ASSET_RATE,
+    MIN_CREATOR_SELL_DELAY, MIN_GRADUATE_THRESHOLD, MIN_PROTOCOL_FEE, NATIVE_MINT,
 };
 
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct TokenSupplyBounds {
+    // The smallest total supply a token can launch with, in whole tokens
+    pub min_token_supply: u64,
+    // The largest total supply a token can launch with, in whole tokens
+    pub max_token_supply: u64,
+    // The most decimals a token mint can have
+    pub max_decimals: u8,
+    // The smallest share of the supply reserved for the LP, in parts per 1_000_000
+    pub min_graduation_allocation: u32,
+    // The largest share of the supply reserved for the LP, in parts per 1_000_000
+    pub max_graduation_allocation: u32,
+}
+
+impl TokenSupplyBounds {
+    pub const DEFAULT: TokenSupplyBounds = TokenSupplyBounds {
+        min_token_supply: DEFAULT_MIN_TOKEN_SUPPLY,
+        max_token_supply: DEFAULT_MAX_TOKEN_SUPPLY,
+        max_decimals: MAX_TOKEN_DECIMALS,
+        min_graduation_allocation: DEFAULT_MIN_GRADUATION_ALLOCATION,
+        max_graduation_allocation: DEFAULT_MAX_GRADUATION_ALLOCATION,
+    };
+}
+
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct AntiSnipeLimit {
+    // The most a single wallet can buy in the window, in parts per 1_000_000 of the token supply
+    pub max_wallet_share: u32,
+    // Seconds after `trading_starts_at` during which the limit applies, 0 disables it. For
+    // auction launches that is when the auction settles, not when the token was created.
+    pub window: u64,
+}
+
+impl AntiSnipeLimit {
+    /// The most tokens one wallet may have bought `elapsed` seconds after trading opened, if capped.
+    pub fn max_wallet_amount(&self, token_supply: u64, elapsed: i64) -> Result<Option<u64>> {
+        if self.window == 0 || elapsed >= self.window as i64 {
+            return Ok(None);
+        }
+        let max_amount = (token_supply as u128)
+            .checked_mul(self.max_wallet_share as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?
+            .checked_div(1_000_000)
+            .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
+        Ok(Some(max_amount))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct MetadataRules {
+    // Keys creators can set as additional token metadata
+    #[max_len(MAX_METADATA_KEYS, MAX_METADATA_KEY_LENGTH)]
+    pub allowed_keys: Vec<String>,
+    // The longest value an additional metadata field can hold
+    pub max_value_length: u16,
+}
+
+impl MetadataRules {
+    /// Checks a creator's additional metadata against the allowed keys and value length.
+    pub fn require_allowed(&self, additional_metadata: &[AdditionalMetadata]) -> Result<()> {
+        for (i, field) in additional_metadata.iter().enumerate() {
+            require!(
+                self.allowed_keys.contains(&field.key),
+                LaunchPadErrorCode::MetadataKeyNotAllowed
+            );
+            require!(
+                additional_metadata[..i]
+                    .iter()
+                    .all(|other| other.key != field.key),
+                LaunchPadErrorCode::DuplicateMetadataKey
+            );
+            require!(
+                !field.value.is_empty() && field.value.len() <= self.max_value_length as usize,
+                LaunchPadErrorCode::InvalidMetadataValueLength
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A key/value pair written to the Token-2022 metadata besides name, symbol and uri.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct AdditionalMetadata {
+    pub key: String,
+    pub value: String,
+}
+
 #[derive(Default, Debug, InitSpace)]
 #[account(discriminator = DISC_LAUNCH_PAD_CONFIG_ACCOUNT)]
 pub struct LaunchPadConfig {
@@ -29,12 +128,36 @@ pub struct LaunchPadConfig {
     pub bump: u8,
     // The vault bump seed for the PDA
     pub vault_bump: u8,
+    // The authority proposed to take over, waiting to accept the transfer
+    pub pending_authority: Pubkey,
+    // The account that receives withdrawn protocol fees, the initial authority by default
+    pub treasury: Pubkey,
+    // The creator's share of each trade fee in basis points (1_000_000 = the whole fee)
+    pub creator_fee_bps: u32,
+    // The referrer's share of the protocol fee in basis points (1_000_000 = the whole fee)
+    pub referral_fee_bps: u32,
+    // The decaying buy fee charged right after trading opens on a token
+    pub launch_fee: LaunchFeeSchedule,
+    // The per wallet buy cap right after trading opens on a token
+    pub anti_snipe: AntiSnipeLimit,
+    // The max share of the curve supply a creator can reserve for team vesting (1_000_000 = 100%)
+    pub max_vesting_share: u32,
+    // The additional metadata creators can attach to their tokens
+    pub metadata_rules: MetadataRules,
+    // Bitmask of the curve types creators can launch tokens on
+    pub allowed_curves: u8,
+    // The supply, decimals and graduation allocation tokens can launch with
+    pub supply_bounds: TokenSupplyBounds,
+    // SPL quote mints tokens can launch against besides native SOL
+    #[max_len(MAX_QUOTE_MINTS)]
+    pub allowed_quote_mints: Vec<Pubkey>,
 }
 
 impl LaunchPadConfig {
     pub const SEED: &'static [u8] = b"launch_pad_config:";
     pub const VAULT_SEED: &'static [u8] = b"vault:";
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         authority: Pubkey,
@@ -54,57 +177,192 @@ impl LaunchPadConfig {
             authority != Pubkey::default(),
             LaunchPadErrorCode::InvalidAuthority
         );
-        require!(
-            creator_sell_delay >= MIN_CREATOR_SELL_DELAY,
-            LaunchPadErrorCode::CreatorSellDelayNotMet
-        );
-        require!(
-            asset_rate > MIN_ASSET_RATE,
-            LaunchPadErrorCode::AssetRateMustBeGreaterThanZero
-        );
-        require!(
-            graduate_threshold > MIN_GRADUATE_THRESHOLD,
-            LaunchPadErrorCode::GraduateThresholdNotMet
-        );
-        require!(
-            protocol_buy_fee <= MAX_PROTOCOL_FEE,
-            LaunchPadErrorCode::ProtocolFeeExceedsMaximum
-        );
-        require!(
-            protocol_buy_fee >= MIN_PROTOCOL_FEE,
-            LaunchPadErrorCode::ProtocolFeeMinimumNotMet
-        );
-        require!(
-            protocol_sell_fee <= MAX_PROTOCOL_FEE,
-            LaunchPadErrorCode::ProtocolFeeExceedsMaximum
-        );
-        require!(
-            protocol_sell_fee >= MIN_PROTOCOL_FEE,
-            LaunchPadErrorCode::ProtocolFeeMinimumNotMet
-        );
+        validate_creator_sell_delay(creator_sell_delay)?;
+        validate_asset_rate(asset_rate)?;
+        validate_graduate_threshold(graduate_threshold)?;
+        validate_protocol_fee(protocol_buy_fee)?;
+        validate_protocol_fee(protocol_sell_fee)?;
         self.authority = authority;
+        self.treasury = authority;
         self.asset_rate = asset_rate;
         self.creator_sell_delay = creator_sell_delay;
         self.graduate_threshold = graduate_threshold;
         self.protocol_buy_fee = protocol_buy_fee;
         self.protocol_sell_fee = protocol_sell_fee;
+        self.allowed_curves = CURVE_CONSTANT_PRODUCT;
+        self.supply_bounds = TokenSupplyBounds::DEFAULT;
         self.status = ProtocolStatus::Active;
         self.bump = bump;
         self.vault_bump = vault_bump;
 
         emit!(LaunchPadConfigInitialized {
             authority: self.authority,
+            treasury: self.treasury,
             asset_rate: self.asset_rate,
             creator_sell_delay: self.creator_sell_delay,
             graduate_threshold: self.graduate_threshold,
             protocol_buy_fee: self.protocol_buy_fee,
             protocol_sell_fee: self.protocol_sell_fee,
+            allowed_curves: self.allowed_curves,
+            supply_bounds: self.supply_bounds,
             status: self.status,
             timestamp: Clock::get()?.unix_timestamp,
         });
         Ok(())
     }
 
+    pub fn update(&mut self, args: &UpdateLaunchPadConfigArgs) -> Result<()> {
+        let new_asset_rate = args.asset_rate.unwrap_or(self.asset_rate);
+        let new_creator_sell_delay = args.creator_sell_delay.unwrap_or(self.creator_sell_delay);
+        let new_graduate_threshold = args.graduate_threshold.unwrap_or(self.graduate_threshold);
+        let new_protocol_buy_fee = args.protocol_buy_fee.unwrap_or(self.protocol_buy_fee);
+        let new_protocol_sell_fee = args.protocol_sell_fee.unwrap_or(self.protocol_sell_fee);
+        let new_treasury = args.treasury.unwrap_or(self.treasury);
+        let new_allowed_curves = args.allowed_curves.unwrap_or(self.allowed_curves);
+        let new_supply_bounds = args.supply_bounds.unwrap_or(self.supply_bounds);
+        let new_creator_fee_bps = args.creator_fee_bps.unwrap_or(self.creator_fee_bps);
+        let new_referral_fee_bps = args.referral_fee_bps.unwrap_or(self.referral_fee_bps);
+        let new_launch_fee = args.launch_fee.unwrap_or(self.launch_fee);
+        let new_anti_snipe = args.anti_snipe.unwrap_or(self.anti_snipe);
+        let new_max_vesting_share = args.max_vesting_share.unwrap_or(self.max_vesting_share);
+        let new_metadata_rules = args
+            .metadata_rules
+            .clone()
+            .unwrap_or_else(|| self.metadata_rules.clone());
+
+        validate_creator_sell_delay(new_creator_sell_delay)?;
+        validate_asset_rate(new_asset_rate)?;
+        validate_graduate_threshold(new_graduate_threshold)?;
+        validate_protocol_fee(new_protocol_buy_fee)?;
+        validate_protocol_fee(new_protocol_sell_fee)?;
+        require!(
+            new_treasury != Pubkey::default(),
+            LaunchPadErrorCode::InvalidTreasury
+        );
+        validate_allowed_curves(new_allowed_curves)?;
+        validate_supply_bounds(&new_supply_bounds)?;
+        require!(
+            new_creator_fee_bps <= MAX_CREATOR_FEE_BPS,
+            LaunchPadErrorCode::CreatorFeeExceedsMaximum
+        );
+        require!(
+            new_referral_fee_bps <= MAX_REFERRAL_FEE_BPS,
+            LaunchPadErrorCode::ReferralFeeExceedsMaximum
+        );
+        new_launch_fee.validate(new_protocol_buy_fee)?;
+        validate_anti_snipe(&new_anti_snipe)?;
+        require!(
+            new_max_vesting_share <= MAX_VESTING_SHARE,
+            LaunchPadErrorCode::VestingShareExceedsMaximum
+        );
+        validate_metadata_rules(&new_metadata_rules)?;
+
+        let event = LaunchPadConfigUpdated {
+            old_asset_rate: self.asset_rate,
+            new_asset_rate,
+            old_creator_sell_delay: self.creator_sell_delay,
+            new_creator_sell_delay,
+            old_graduate_threshold: self.graduate_threshold,
+            new_graduate_threshold,
+            old_protocol_buy_fee: self.protocol_buy_fee,
+            new_protocol_buy_fee,
+            old_protocol_sell_fee: self.protocol_sell_fee,
+            new_protocol_sell_fee,
+            old_treasury: self.treasury,
+            new_treasury,
+            old_allowed_curves: self.allowed_curves,
+            new_allowed_curves,
+            old_supply_bounds: self.supply_bounds,
+            new_supply_bounds,
+            old_creator_fee_bps: self.creator_fee_bps,
+            new_creator_fee_bps,
+            old_referral_fee_bps: self.referral_fee_bps,
+            new_referral_fee_bps,
+            old_launch_fee: self.launch_fee,
+            new_launch_fee,
+            old_anti_snipe: self.anti_snipe,
+            new_anti_snipe,
+            old_max_vesting_share: self.max_vesting_share,
+            new_max_vesting_share,
+            old_metadata_rules: self.metadata_rules.clone(),
+            new_metadata_rules: new_metadata_rules.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+
+        self.asset_rate = new_asset_rate;
+        self.creator_sell_delay = new_creator_sell_delay;
+        self.graduate_threshold = new_graduate_threshold;
+        self.protocol_buy_fee = new_protocol_buy_fee;
+        self.protocol_sell_fee = new_protocol_sell_fee;
+        self.treasury = new_treasury;
+        self.allowed_curves = new_allowed_curves;
+        self.supply_bounds = new_supply_bounds;
+        self.creator_fee_bps = new_creator_fee_bps;
+        self.referral_fee_bps = new_referral_fee_bps;
+        self.launch_fee = new_launch_fee;
+        self.anti_snipe = new_anti_snipe;
+        self.max_vesting_share = new_max_vesting_share;
+        self.metadata_rules = new_metadata_rules;
+
+        emit!(event);
+        Ok(())
+    }
+
+    pub fn propose_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require!(
+            new_authority != Pubkey::default() && new_authority != self.authority,
+            LaunchPadErrorCode::InvalidAuthority
+        );
+
+        self.pending_authority = new_authority;
+
+        emit!(LaunchPadAuthorityProposed {
+            authority: self.authority,
+            pending_authority: self.pending_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn accept_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require!(
+            self.pending_authority != Pubkey::default(),
+            LaunchPadErrorCode::NoPendingAuthority
+        );
+        require!(
+            self.pending_authority == new_authority,
+            LaunchPadErrorCode::InvalidPendingAuthority
+        );
+
+        let old_authority = self.authority;
+        self.authority = new_authority;
+        self.pending_authority = Pubkey::default();
+
+        emit!(LaunchPadAuthorityAccepted {
+            old_authority,
+            new_authority: self.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(&mut self) -> Result<()> {
+        require!(
+            self.pending_authority != Pubkey::default(),
+            LaunchPadErrorCode::NoPendingAuthority
+        );
+
+        let pending_authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+
+        emit!(LaunchPadAuthorityTransferCancelled {
+            authority: self.authority,
+            pending_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
     pub fn pause(&mut self) -> Result<()> {
         // Check protocol is not already paused.
         require!(
@@ -134,21 +392,995 @@ impl LaunchPadConfig {
         Ok(())
     }
 
+    pub fn add_quote_mint(&mut self, quote_mint: Pubkey) -> Result<()> {
+        require!(
+            quote_mint != Pubkey::default() && quote_mint != NATIVE_MINT,
+            LaunchPadErrorCode::InvalidQuoteMint
+        );
+        require!(
+            !self.allowed_quote_mints.contains(&quote_mint),
+            LaunchPadErrorCode::QuoteMintAlreadyAllowed
+        );
+        require!(
+            self.allowed_quote_mints.len() < MAX_QUOTE_MINTS,
+            LaunchPadErrorCode::TooManyQuoteMints
+        );
+
+        self.allowed_quote_mints.push(quote_mint);
+
+        emit!(LaunchPadQuoteMintAdded {
+            quote_mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn remove_quote_mint(&mut self, quote_mint: Pubkey) -> Result<()> {
+        let index = self
+            .allowed_quote_mints
+            .iter()
+            .position(|allowed| *allowed == quote_mint)
+            .ok_or(LaunchPadErrorCode::QuoteMintNotAllowed)?;
+
+        self.allowed_quote_mints.remove(index);
+
+        emit!(LaunchPadQuoteMintRemoved {
+            quote_mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Native SOL is always allowed, SPL quote mints must be allowlisted.
+    pub fn require_quote_mint_allowed(&self, quote_mint: &Pubkey) -> Result<()> {
+        require!(
+            *quote_mint == NATIVE_MINT || self.allowed_quote_mints.contains(quote_mint),
+            LaunchPadErrorCode::QuoteMintNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Checks that a launch may be created on `curve_type` and priced in `quote_mint`,
+    /// returning the mint the curve is priced in.
+    pub fn require_launch_allowed(
+        &self,
+        curve_type: &CurveType,
+        quote_mint: Option<Pubkey>,
+    ) -> Result<Pubkey> {
+        self.require_active()?;
+        self.require_curve_allowed(curve_type)?;
+        match quote_mint {
+            Some(quote_mint) => {
+                self.require_quote_mint_allowed(&quote_mint)?;
+                Ok(quote_mint)
+            }
+            None => Ok(NATIVE_MINT),
+        }
+    }
+
+    pub fn require_curve_allowed(&self, curve_type: &CurveType) -> Result<()> {
+        require!(
+            self.allowed_curves & curve_type.mask() != 0,
+            LaunchPadErrorCode::CurveTypeNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Checks a launch against the supply bounds and returns the total supply
+    /// and graduation allocation in base units.
+    pub fn calculate_token_supply(
+        &self,
+        token_supply: u64,
+        decimals: u8,
+        graduation_allocation: u32,
+    ) -> Result<(u64, u64)> {
+        let bounds = &self.supply_bounds;
+        require!(
+            token_supply >= bounds.min_token_supply && token_supply <= bounds.max_token_supply,
+            LaunchPadErrorCode::InvalidTokenSupply
+        );
+        require!(
+            decimals <= bounds.max_decimals,
+            LaunchPadErrorCode::InvalidTokenDecimals
+        );
+        require!(
+            graduation_allocation >= bounds.min_graduation_allocation
+                && graduation_allocation <= bounds.max_graduation_allocation,
+            LaunchPadErrorCode::InvalidGraduationAllocation
+        );
+
+        let total_supply = 10u64
+            .checked_pow(decimals as u32)
+            .and_then(|unit| token_supply.checked_mul(unit))
+            .ok_or(LaunchPadErrorCode::InvalidTokenSupply)?;
+        let graduation_amount = (total_supply as u128)
+            .checked_mul(graduation_allocation as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?
+            .checked_div(MAX_GRADUATION_ALLOCATION as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
+        Ok((total_supply, graduation_amount))
+    }
+
+    /// Fills the fields appended after the first release with the values `initialize` sets,
+    /// once `migrate_launch_pad_config` has grown the account.
+    pub fn migrate(&mut self) -> Result<()> {
+        self.treasury = self.authority;
+        self.allowed_curves = CURVE_CONSTANT_PRODUCT;
+        self.supply_bounds = TokenSupplyBounds::DEFAULT;
+        Ok(())
+    }
+
+    pub fn require_active(&self) -> Result<()> {
+        require!(
+            self.status == ProtocolStatus::Active,
+            LaunchPadErrorCode::ProtocolConfigNotActive
+        );
+        Ok(())
+    }
+
     pub fn calculate_buy_fee(&self, amount: u64) -> Result<u64> {
-        let fee = (amount as u128)
-            .checked_mul(self.protocol_buy_fee as u128)
+        Ok(calculate_fee(amount, self.protocol_buy_fee)?)
+    }
+
+    /// Returns the smallest amount whose buy fee leaves at least `net_amount` for the curve.
+    pub fn calculate_buy_amount_with_fee(&self, net_amount: u64) -> Result<u64> {
+        Ok(calculate_amount_with_fee(
+            net_amount,
+            self.protocol_buy_fee,
+        )?)
+    }
+
+    /// Buy fee rate for a token whose trading opened at `trading_starts_at`, following the
+    /// launch fee schedule.
+    pub fn buy_fee_at(&self, trading_starts_at: i64, now: i64) -> Result<u32> {
+        let elapsed = now
+            .checked_sub(trading_starts_at)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok(self.launch_fee.buy_fee_at(self.protocol_buy_fee, elapsed)?)
+    }
+
+    /// Returns the creator's share of a trade fee, the rest goes to the protocol vault.
+    pub fn calculate_creator_fee(&self, fee: u64) -> Result<u64> {
+        let creator_fee = (fee as u128)
+            .checked_mul(self.creator_fee_bps as u128)
             .ok_or(LaunchPadErrorCode::MathOverflow)?
             .checked_div(1_000_000)
             .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
-        Ok(fee)
+        Ok(creator_fee)
     }
 
-    pub fn calculate_sell_fee(&self, amount: u64) -> Result<u64> {
-        let fee = (amount as u128)
-            .checked_mul(self.protocol_sell_fee as u128)
+    /// Returns the tokens reserved for team vesting out of the curve supply.
+    pub fn calculate_vesting_amount(&self, token_supply: u64, vesting_share: u32) -> Result<u64> {
+        require!(
+            vesting_share <= self.max_vesting_share,
+            LaunchPadErrorCode::VestingShareExceedsMaximum
+        );
+        let vesting_amount = (token_supply as u128)
+            .checked_mul(vesting_share as u128)
             .ok_or(LaunchPadErrorCode::MathOverflow)?
             .checked_div(1_000_000)
             .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
-        Ok(fee)
+        Ok(vesting_amount)
+    }
+
+    /// Returns the referrer's share of the protocol part of a trade fee.
+    pub fn calculate_referral_fee(&self, protocol_fee: u64) -> Result<u64> {
+        let referral_fee = (protocol_fee as u128)
+            .checked_mul(self.referral_fee_bps as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?
+            .checked_div(1_000_000)
+            .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
+        Ok(referral_fee)
+    }
+
+    pub fn calculate_sell_fee(&self, amount: u64) -> Result<u64> {
+        Ok(calculate_fee(amount, self.protocol_sell_fee)?)
+    }
+}
+
+fn validate_asset_rate(asset_rate: u64) -> Result<()> {
+    require!(
+        asset_rate > MIN_ASSET_RATE,
+        LaunchPadErrorCode::AssetRateMustBeGreaterThanZero
+    );
+    Ok(())
+}
+
+fn validate_creator_sell_delay(creator_sell_delay: u64) -> Result<()> {
+    require!(
+        creator_sell_delay >= MIN_CREATOR_SELL_DELAY,
+        LaunchPadErrorCode::CreatorSellDelayNotMet
+    );
+    Ok(())
+}
+
+fn validate_graduate_threshold(graduate_threshold: u64) -> Result<()> {
+    require!(
+        graduate_threshold > MIN_GRADUATE_THRESHOLD,
+        LaunchPadErrorCode::GraduateThresholdNotMet
+    );
+    Ok(())
+}
+
+fn validate_allowed_curves(allowed_curves: u8) -> Result<()> {
+    require!(
+        allowed_curves != 0 && allowed_curves & !ALL_CURVES == 0,
+        LaunchPadErrorCode::InvalidAllowedCurves
+    );
+    Ok(())
+}
+
+fn validate_supply_bounds(bounds: &TokenSupplyBounds) -> Result<()> {
+    require!(
+        bounds.min_token_supply > 0
+            && bounds.min_token_supply <= bounds.max_token_supply
+            && bounds.max_decimals <= MAX_TOKEN_DECIMALS
+            && bounds.min_graduation_allocation > 0
+            && bounds.min_graduation_allocation <= bounds.max_graduation_allocation
+            && bounds.max_graduation_allocation < MAX_GRADUATION_ALLOCATION,
+        LaunchPadErrorCode::InvalidTokenSupplyBounds
+    );
+    Ok(())
+}
+
+fn validate_anti_snipe(anti_snipe: &AntiSnipeLimit) -> Result<()> {
+    require!(
+        anti_snipe.window == 0
+            || (anti_snipe.max_wallet_share > 0 && anti_snipe.max_wallet_share <= MAX_WALLET_SHARE),
+        LaunchPadErrorCode::InvalidAntiSnipeLimit
+    );
+    Ok(())
+}
+
+fn validate_metadata_rules(rules: &MetadataRules) -> Result<()> {
+    require!(
+        rules.allowed_keys.len() <= MAX_METADATA_KEYS
+            && rules.max_value_length <= MAX_METADATA_VALUE_LENGTH,
+        LaunchPadErrorCode::InvalidMetadataRules
+    );
+    for (i, key) in rules.allowed_keys.iter().enumerate() {
+        require!(
+            !key.is_empty()
+                && key.len() <= MAX_METADATA_KEY_LENGTH
+                && !rules.allowed_keys[..i].contains(key),
+            LaunchPadErrorCode::InvalidMetadataRules
+        );
+    }
+    Ok(())
+}
+
+fn validate_protocol_fee(protocol_fee: u32) -> Result<()> {
+    require!(
+        protocol_fee <= MAX_PROTOCOL_FEE,
+        LaunchPadErrorCode::ProtocolFeeExceedsMaximum
+    );
+    require!(
+        protocol_fee >= MIN_PROTOCOL_FEE,
+        LaunchPadErrorCode::ProtocolFeeMinimumNotMet
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{test_utils::warp_to, FeeDecay, CURVE_LINEAR};
+
+    use super::*;
+
+    fn active_config() -> LaunchPadConfig {
+        LaunchPadConfig {
+            authority: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            asset_rate: 300_000,
+            creator_sell_delay: MIN_CREATOR_SELL_DELAY,
+            graduate_threshold: 85_000_000_000,
+            protocol_buy_fee: 5_000,
+            protocol_sell_fee: 7_000,
+            allowed_curves: CURVE_CONSTANT_PRODUCT,
+            supply_bounds: TokenSupplyBounds::DEFAULT,
+            status: ProtocolStatus::Active,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pause_and_unpause() {
+        warp_to(1_000);
+        let mut config = active_config();
+
+        config.pause().unwrap();
+        assert_eq!(config.status, ProtocolStatus::Paused);
+        assert_eq!(
+            config.pause().unwrap_err(),
+            LaunchPadErrorCode::ProtocolAlreadyPaused.into()
+        );
+
+        config.unpause().unwrap();
+        assert_eq!(config.status, ProtocolStatus::Active);
+        assert_eq!(
+            config.unpause().unwrap_err(),
+            LaunchPadErrorCode::ProtocolNotPaused.into()
+        );
+    }
+
+    #[test]
+    fn test_paused_protocol_blocks_create_token() {
+        warp_to(1_000);
+        let mut config = active_config();
+        assert_eq!(
+            config
+                .require_launch_allowed(&CurveType::ConstantProduct, None)
+                .unwrap(),
+            NATIVE_MINT
+        );
+
+        config.pause().unwrap();
+        assert_eq!(
+            config
+                .require_launch_allowed(&CurveType::ConstantProduct, None)
+                .unwrap_err(),
+            LaunchPadErrorCode::ProtocolConfigNotActive.into()
+        );
+
+        config.unpause().unwrap();
+        config
+            .require_launch_allowed(&CurveType::ConstantProduct, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_migrate_first_release_layout() {
+        let authority = Pubkey::new_unique();
+        let mut data = LaunchPadConfig::DISCRIMINATOR.to_vec();
+        (
+            authority,
+            300_000u64,
+            MIN_CREATOR_SELL_DELAY,
+            85_000_000_000u64,
+            5_000u32,
+            7_000u32,
+            ProtocolStatus::Paused,
+            254u8,
+            253u8,
+        )
+            .serialize(&mut data)
+            .unwrap();
+        data.resize(
+            LaunchPadConfig::DISCRIMINATOR.len() + LaunchPadConfig::INIT_SPACE,
+            0,
+        );
+
+        let mut config = LaunchPadConfig::try_deserialize(&mut &data[..]).unwrap();
+        config.migrate().unwrap();
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.asset_rate, 300_000);
+        assert_eq!(config.creator_sell_delay, MIN_CREATOR_SELL_DELAY);
+        assert_eq!(config.graduate_threshold, 85_000_000_000);
+        assert_eq!(config.protocol_buy_fee, 5_000);
+        assert_eq!(config.protocol_sell_fee, 7_000);
+        assert_eq!(config.status, ProtocolStatus::Paused);
+        assert_eq!((config.bump, config.vault_bump), (254, 253));
+        assert_eq!(config.pending_authority, Pubkey::default());
+        assert_eq!(config.treasury, authority);
+        assert_eq!(config.allowed_curves, CURVE_CONSTANT_PRODUCT);
+        assert_eq!(config.supply_bounds, TokenSupplyBounds::DEFAULT);
+        assert!(config.allowed_quote_mints.is_empty());
+        config.try_serialize(&mut &mut data[..]).unwrap();
+    }
+
+    #[test]
+    fn test_update_subset_of_fields() {
+        warp_to(1_000);
+        let mut config = active_config();
+
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                creator_sell_delay: Some(7_200),
+                protocol_buy_fee: Some(MAX_PROTOCOL_FEE),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(config.asset_rate, 300_000);
+        assert_eq!(config.creator_sell_delay, 7_200);
+        assert_eq!(config.graduate_threshold, 85_000_000_000);
+        assert_eq!(config.protocol_buy_fee, MAX_PROTOCOL_FEE);
+        assert_eq!(config.protocol_sell_fee, 7_000);
+    }
+
+    #[test]
+    fn test_update_treasury() {
+        warp_to(1_000);
+        let mut config = active_config();
+        let treasury = Pubkey::new_unique();
+
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                treasury: Some(treasury),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.treasury, treasury);
+
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    treasury: Some(Pubkey::default()),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidTreasury.into()
+        );
+    }
+
+    #[test]
+    fn test_update_applies_initialize_bounds() {
+        warp_to(1_000);
+        let mut config = active_config();
+
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    protocol_buy_fee: Some(MAX_PROTOCOL_FEE + 1),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::ProtocolFeeExceedsMaximum.into()
+        );
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    protocol_sell_fee: Some(MIN_PROTOCOL_FEE - 1),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::ProtocolFeeMinimumNotMet.into()
+        );
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    creator_sell_delay: Some(MIN_CREATOR_SELL_DELAY - 1),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::CreatorSellDelayNotMet.into()
+        );
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    asset_rate: Some(MIN_ASSET_RATE),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::AssetRateMustBeGreaterThanZero.into()
+        );
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    graduate_threshold: Some(MIN_GRADUATE_THRESHOLD),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::GraduateThresholdNotMet.into()
+        );
+
+        // A rejected update leaves every field untouched.
+        assert_eq!(config.protocol_buy_fee, 5_000);
+        assert_eq!(config.protocol_sell_fee, 7_000);
+        assert_eq!(config.creator_sell_delay, MIN_CREATOR_SELL_DELAY);
+    }
+
+    #[test]
+    fn test_authority_transfer() {
+        warp_to(1_000);
+        let mut config = active_config();
+        let old_authority = config.authority;
+        let new_authority = Pubkey::new_unique();
+
+        config.propose_authority(new_authority).unwrap();
+        // The current authority stays in control until the transfer is accepted.
+        assert_eq!(config.authority, old_authority);
+        assert_eq!(config.pending_authority, new_authority);
+
+        assert_eq!(
+            config.accept_authority(Pubkey::new_unique()).unwrap_err(),
+            LaunchPadErrorCode::InvalidPendingAuthority.into()
+        );
+
+        config.accept_authority(new_authority).unwrap();
+        assert_eq!(config.authority, new_authority);
+        assert_eq!(config.pending_authority, Pubkey::default());
+
+        assert_eq!(
+            config.accept_authority(new_authority).unwrap_err(),
+            LaunchPadErrorCode::NoPendingAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_authority_transfer_cancel() {
+        warp_to(1_000);
+        let mut config = active_config();
+        let old_authority = config.authority;
+        let typo_authority = Pubkey::new_unique();
+
+        assert_eq!(
+            config.cancel_authority_transfer().unwrap_err(),
+            LaunchPadErrorCode::NoPendingAuthority.into()
+        );
+
+        config.propose_authority(typo_authority).unwrap();
+        config.cancel_authority_transfer().unwrap();
+        assert_eq!(config.authority, old_authority);
+        assert_eq!(config.pending_authority, Pubkey::default());
+
+        assert_eq!(
+            config.accept_authority(typo_authority).unwrap_err(),
+            LaunchPadErrorCode::NoPendingAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_propose_authority_rejects_invalid_keys() {
+        warp_to(1_000);
+        let mut config = active_config();
+
+        assert_eq!(
+            config.propose_authority(Pubkey::default()).unwrap_err(),
+            LaunchPadErrorCode::InvalidAuthority.into()
+        );
+        assert_eq!(
+            config.propose_authority(config.authority).unwrap_err(),
+            LaunchPadErrorCode::InvalidAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_calculate_buy_amount_with_fee() {
+        let config = active_config();
+
+        for net_amount in [0, 1, 199, 995_000_000, 1_000_000_000, 109_535_269_847] {
+            let amount = config.calculate_buy_amount_with_fee(net_amount).unwrap();
+            let fee = config.calculate_buy_fee(amount).unwrap();
+            assert!(amount - fee >= net_amount);
+        }
+        assert_eq!(
+            config.calculate_buy_amount_with_fee(995_000_000).unwrap(),
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_creator_fee_bps() {
+        warp_to(1_000);
+        let mut config = active_config();
+        assert_eq!(config.calculate_creator_fee(5_000_000).unwrap(), 0);
+
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                creator_fee_bps: Some(250_000),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.creator_fee_bps, 250_000);
+        assert_eq!(config.calculate_creator_fee(5_000_000).unwrap(), 1_250_000);
+
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    creator_fee_bps: Some(MAX_CREATOR_FEE_BPS + 1),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::CreatorFeeExceedsMaximum.into()
+        );
+    }
+
+    #[test]
+    fn test_referral_fee_bps() {
+        warp_to(1_000);
+        let mut config = active_config();
+        assert_eq!(config.calculate_referral_fee(5_000_000).unwrap(), 0);
+
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                referral_fee_bps: Some(100_000),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.referral_fee_bps, 100_000);
+        assert_eq!(config.calculate_referral_fee(4_000_000).unwrap(), 400_000);
+
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    referral_fee_bps: Some(MAX_REFERRAL_FEE_BPS + 1),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::ReferralFeeExceedsMaximum.into()
+        );
+    }
+
+    #[test]
+    fn test_launch_fee_schedule() {
+        warp_to(1_000);
+        let mut config = active_config();
+        assert_eq!(config.buy_fee_at(1_000, 1_000).unwrap(), 5_000);
+
+        let schedule = LaunchFeeSchedule {
+            start_fee: 500_000,
+            window: 60,
+            decay: FeeDecay::Linear,
+        };
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                launch_fee: Some(schedule),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.launch_fee, schedule);
+        assert_eq!(config.buy_fee_at(1_000, 1_000).unwrap(), 500_000);
+        assert_eq!(config.buy_fee_at(1_000, 1_030).unwrap(), 252_500);
+        assert_eq!(config.buy_fee_at(1_000, 1_060).unwrap(), 5_000);
+
+        // the schedule cannot start below a raised base fee
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    protocol_buy_fee: Some(MAX_PROTOCOL_FEE),
+                    launch_fee: Some(LaunchFeeSchedule {
+                        start_fee: MAX_PROTOCOL_FEE - 1,
+                        ..schedule
+                    }),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidLaunchFeeSchedule.into()
+        );
+    }
+
+    #[test]
+    fn test_max_vesting_share() {
+        warp_to(1_000);
+        let mut config = active_config();
+        assert_eq!(
+            config
+                .calculate_vesting_amount(1_000_000_000, 1)
+                .unwrap_err(),
+            LaunchPadErrorCode::VestingShareExceedsMaximum.into()
+        );
+
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                max_vesting_share: Some(100_000),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            config
+                .calculate_vesting_amount(1_000_000_000, 100_000)
+                .unwrap(),
+            100_000_000
+        );
+
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    max_vesting_share: Some(MAX_VESTING_SHARE + 1),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::VestingShareExceedsMaximum.into()
+        );
+    }
+
+    #[test]
+    fn test_anti_snipe_limit() {
+        let limit = AntiSnipeLimit {
+            max_wallet_share: 10_000,
+            window: 300,
+        };
+        assert_eq!(
+            limit.max_wallet_amount(1_000_000_000, 0).unwrap(),
+            Some(10_000_000)
+        );
+        assert_eq!(
+            limit.max_wallet_amount(1_000_000_000, 299).unwrap(),
+            Some(10_000_000)
+        );
+        assert_eq!(limit.max_wallet_amount(1_000_000_000, 300).unwrap(), None);
+        assert_eq!(
+            AntiSnipeLimit::default()
+                .max_wallet_amount(1_000_000_000, 0)
+                .unwrap(),
+            None
+        );
+
+        assert!(validate_anti_snipe(&limit).is_ok());
+        assert!(validate_anti_snipe(&AntiSnipeLimit::default()).is_ok());
+        for invalid in [
+            AntiSnipeLimit {
+                max_wallet_share: 0,
+                window: 300,
+            },
+            AntiSnipeLimit {
+                max_wallet_share: MAX_WALLET_SHARE + 1,
+                window: 300,
+            },
+        ] {
+            assert_eq!(
+                validate_anti_snipe(&invalid).unwrap_err(),
+                LaunchPadErrorCode::InvalidAntiSnipeLimit.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_allowed_curves() {
+        warp_to(1_000);
+        let mut config = active_config();
+        assert!(config
+            .require_curve_allowed(&CurveType::ConstantProduct)
+            .is_ok());
+
+        let linear = CurveType::Linear {
+            initial_price: 28_000_000_000,
+            slope: 100_000_000_000,
+        };
+        assert_eq!(
+            config.require_curve_allowed(&linear).unwrap_err(),
+            LaunchPadErrorCode::CurveTypeNotAllowed.into()
+        );
+
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                allowed_curves: Some(CURVE_CONSTANT_PRODUCT | CURVE_LINEAR),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(config.require_curve_allowed(&linear).is_ok());
+
+        for allowed_curves in [0, ALL_CURVES + 1] {
+            assert_eq!(
+                config
+                    .update(&UpdateLaunchPadConfigArgs {
+                        allowed_curves: Some(allowed_curves),
+                        ..Default::default()
+                    })
+                    .unwrap_err(),
+                LaunchPadErrorCode::InvalidAllowedCurves.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_calculate_token_supply() {
+        let config = active_config();
+
+        // The original launch: 1 billion tokens, 9 decimals and 20% for the LP.
+        assert_eq!(
+            config
+                .calculate_token_supply(1_000_000_000, 9, 200_000)
+                .unwrap(),
+            (1_000_000_000_000_000_000, 200_000_000_000_000_000)
+        );
+        // 100 million tokens, 6 decimals and a 30% LP reserve.
+        assert_eq!(
+            config
+                .calculate_token_supply(100_000_000, 6, 300_000)
+                .unwrap(),
+            (100_000_000_000_000, 30_000_000_000_000)
+        );
+
+        assert_eq!(
+            config
+                .calculate_token_supply(DEFAULT_MIN_TOKEN_SUPPLY - 1, 9, 200_000)
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidTokenSupply.into()
+        );
+        assert_eq!(
+            config
+                .calculate_token_supply(DEFAULT_MAX_TOKEN_SUPPLY + 1, 9, 200_000)
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidTokenSupply.into()
+        );
+        assert_eq!(
+            config
+                .calculate_token_supply(1_000_000_000, MAX_TOKEN_DECIMALS + 1, 200_000)
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidTokenDecimals.into()
+        );
+        assert_eq!(
+            config
+                .calculate_token_supply(1_000_000_000, 9, DEFAULT_MAX_GRADUATION_ALLOCATION + 1)
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidGraduationAllocation.into()
+        );
+    }
+
+    #[test]
+    fn test_update_supply_bounds() {
+        warp_to(1_000);
+        let mut config = active_config();
+        let bounds = TokenSupplyBounds {
+            min_token_supply: 100_000_000,
+            max_token_supply: 100_000_000,
+            max_decimals: 6,
+            min_graduation_allocation: 300_000,
+            max_graduation_allocation: 300_000,
+        };
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                supply_bounds: Some(bounds),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.supply_bounds, bounds);
+
+        for invalid in [
+            TokenSupplyBounds {
+                min_token_supply: 0,
+                ..bounds
+            },
+            TokenSupplyBounds {
+                max_token_supply: 99_999_999,
+                ..bounds
+            },
+            TokenSupplyBounds {
+                max_decimals: MAX_TOKEN_DECIMALS + 1,
+                ..bounds
+            },
+            TokenSupplyBounds {
+                max_graduation_allocation: MAX_GRADUATION_ALLOCATION,
+                ..bounds
+            },
+        ] {
+            assert_eq!(
+                config
+                    .update(&UpdateLaunchPadConfigArgs {
+                        supply_bounds: Some(invalid),
+                        ..Default::default()
+                    })
+                    .unwrap_err(),
+                LaunchPadErrorCode::InvalidTokenSupplyBounds.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_quote_mint_allowlist() {
+        warp_to(1_000);
+        let mut config = active_config();
+        let usdc = Pubkey::new_unique();
+
+        assert!(config.require_quote_mint_allowed(&NATIVE_MINT).is_ok());
+        assert_eq!(
+            config.require_quote_mint_allowed(&usdc).unwrap_err(),
+            LaunchPadErrorCode::QuoteMintNotAllowed.into()
+        );
+
+        config.add_quote_mint(usdc).unwrap();
+        assert!(config.require_quote_mint_allowed(&usdc).is_ok());
+        assert_eq!(
+            config.add_quote_mint(usdc).unwrap_err(),
+            LaunchPadErrorCode::QuoteMintAlreadyAllowed.into()
+        );
+        assert_eq!(
+            config.add_quote_mint(NATIVE_MINT).unwrap_err(),
+            LaunchPadErrorCode::InvalidQuoteMint.into()
+        );
+
+        config.remove_quote_mint(usdc).unwrap();
+        assert_eq!(
+            config.require_quote_mint_allowed(&usdc).unwrap_err(),
+            LaunchPadErrorCode::QuoteMintNotAllowed.into()
+        );
+        assert_eq!(
+            config.remove_quote_mint(usdc).unwrap_err(),
+            LaunchPadErrorCode::QuoteMintNotAllowed.into()
+        );
+
+        for _ in 0..MAX_QUOTE_MINTS {
+            config.add_quote_mint(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            config.add_quote_mint(usdc).unwrap_err(),
+            LaunchPadErrorCode::TooManyQuoteMints.into()
+        );
+    }
+
+    #[test]
+    fn test_metadata_rules() {
+        warp_to(1_000);
+        let mut config = active_config();
+        let field = |key: &str, value: &str| AdditionalMetadata {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        assert!(config.metadata_rules.require_allowed(&[]).is_ok());
+        assert_eq!(
+            config
+                .metadata_rules
+                .require_allowed(&[field("website", "https://example.com")])
+                .unwrap_err(),
+            LaunchPadErrorCode::MetadataKeyNotAllowed.into()
+        );
+
+        let rules = MetadataRules {
+            allowed_keys: vec!["website".to_string(), "twitter".to_string()],
+            max_value_length: 32,
+        };
+        config
+            .update(&UpdateLaunchPadConfigArgs {
+                metadata_rules: Some(rules.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.metadata_rules, rules);
+        assert!(config
+            .metadata_rules
+            .require_allowed(&[
+                field("website", "https://example.com"),
+                field("twitter", "@example")
+            ])
+            .is_ok());
+        assert_eq!(
+            config
+                .metadata_rules
+                .require_allowed(&[field("telegram", "example")])
+                .unwrap_err(),
+            LaunchPadErrorCode::MetadataKeyNotAllowed.into()
+        );
+        assert_eq!(
+            config
+                .metadata_rules
+                .require_allowed(&[field("website", "a"), field("website", "b")])
+                .unwrap_err(),
+            LaunchPadErrorCode::DuplicateMetadataKey.into()
+        );
+        for value in ["", &"a".repeat(33)] {
+            assert_eq!(
+                config
+                    .metadata_rules
+                    .require_allowed(&[field("website", value)])
+                    .unwrap_err(),
+                LaunchPadErrorCode::InvalidMetadataValueLength.into()
+            );
+        }
+
+        for allowed_keys in [
+            vec![String::new()],
+            vec!["a".repeat(MAX_METADATA_KEY_LENGTH + 1)],
+            vec!["website".to_string(), "website".to_string()],
+            (0..=MAX_METADATA_KEYS).map(|i| i.to_string()).collect(),
+        ] {
+            let rules = MetadataRules {
+                allowed_keys,
+                max_value_length: 32,
+            };
+            assert_eq!(
+                config
+                    .update(&UpdateLaunchPadConfigArgs {
+                        metadata_rules: Some(rules),
+                        ..Default::default()
+                    })
+                    .unwrap_err(),
+                LaunchPadErrorCode::InvalidMetadataRules.into()
+            );
+        }
+        let rules = MetadataRules {
+            allowed_keys: vec![],
+            max_value_length: MAX_METADATA_VALUE_LENGTH + 1,
+        };
+        assert_eq!(
+            config
+                .update(&UpdateLaunchPadConfigArgs {
+                    metadata_rules: Some(rules),
+                    ..Default::default()
+                })
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidMetadataRules.into()
+        );
     }
 }
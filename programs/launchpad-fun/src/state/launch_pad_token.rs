@@ -3,7 +3,11 @@ use anchor_lang::prelude::*;
 use anchor_lang::{account, prelude::Pubkey, InitSpace};
 
 use crate::{
-    LaunchPadErrorCode, LaunchPadTokenCreated, LaunchPadTokenStatus, DISC_LAUNCH_PAD_TOKEN_ACCOUNT,
+    allowlist_leaf, calculate_amount_with_fee, calculate_fee, verify_allowlist_proof, Allowlist,
+    AllowlistProof, BondingCurve, BuySplit, CreatorFeesAccrued, CurveReserves, CurveType,
+    LaunchPadConfig, LaunchPadErrorCode, LaunchPadTokenBought, LaunchPadTokenCreated,
+    LaunchPadTokenStatus, TradeQuote, DISC_LAUNCH_PAD_TOKEN_ACCOUNT, LEGACY_TOKEN_DECIMALS,
+    NATIVE_MINT, TOKEN_GRADUATION_AMOUNT, TOKEN_TOTAL_SUPPLY,
 };
 
 #[derive(Default, Debug, InitSpace)]
@@ -17,7 +21,7 @@ pub struct LaunchPadToken {
     pub virtual_token_amount: u64,
     /// Total virtual reserve of the asset
     pub virtual_asset_amount: u64,
-    /// The liquidity pool invariant k = x * y
+    /// The liquidity pool invariant k = x * y, 0 for curves other than constant product
     pub current_k: u128,
     /// Total virtual reserve amount for graduation
     pub virtual_graduation_amount: u64,
@@ -31,6 +35,24 @@ pub struct LaunchPadToken {
     pub bump: u8,
     // The vault graduation bump seed for the PDA
     pub vault_bump: u8,
+    /// The mint the curve is priced in, the wSOL mint for native SOL
+    pub quote_mint: Pubkey,
+    /// Total supply of the token in base units
+    pub token_supply: u64,
+    /// Decimals of the token mint
+    pub decimals: u8,
+    /// Tokens reserved for the liquidity pool at graduation
+    pub graduation_token_amount: u64,
+    /// The bonding curve the token trades on
+    pub curve_type: CurveType,
+    /// Creator share of trade fees accrued and not yet claimed
+    pub creator_fees: u64,
+    /// The timestamp when trading opens, equal to created_at unless scheduled
+    pub trading_starts_at: i64,
+    /// Merkle root of the presale allowlist, zeroed when there is none
+    pub allowlist_root: [u8; 32],
+    /// The timestamp when the allowlist presale ends
+    pub allowlist_ends_at: i64,
 }
 
 impl LaunchPadToken {
@@ -39,12 +61,19 @@ impl LaunchPadToken {
     pub const VAULT_TOKEN_GRADUATION_SEED: &'static [u8] = b"vault_gdt_tk:";
     pub const VAULT_ASSET_GRADUATION_SEED: &'static [u8] = b"vault_asset_gdt:";
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &mut self,
         creator: Pubkey,
         mint: Pubkey,
+        quote_mint: Pubkey,
         token_amount: u64,
+        decimals: u8,
+        graduation_token_amount: u64,
         asset_amount: u64,
+        curve_type: CurveType,
+        trading_starts_at: Option<i64>,
+        allowlist: Option<Allowlist>,
         bump: u8,
         vault_bump: u8,
     ) -> Result<()> {
@@ -57,22 +86,66 @@ impl LaunchPadToken {
             LaunchPadErrorCode::InvalidCreator
         );
         require!(mint != Pubkey::default(), LaunchPadErrorCode::InvalidMint);
+        require!(
+            graduation_token_amount < token_amount,
+            LaunchPadErrorCode::InvalidGraduationAllocation
+        );
+        curve_type.validate(token_amount as u128)?;
         self.creator = creator;
         self.mint = mint;
+        self.quote_mint = quote_mint;
+        self.token_supply = token_amount;
+        self.decimals = decimals;
+        self.graduation_token_amount = graduation_token_amount;
         self.virtual_token_amount = token_amount;
         self.virtual_asset_amount = asset_amount;
         self.current_k = (token_amount as u128)
             .checked_mul(asset_amount as u128)
             .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        // x * y = 0 would hand out the whole reserve for any amount.
+        require!(
+            self.current_k > 0 || curve_type != CurveType::ConstantProduct,
+            LaunchPadErrorCode::InvalidCurveParams
+        );
+        self.curve_type = curve_type;
         self.virtual_graduation_amount = 0;
         self.created_at = Clock::get()?.unix_timestamp;
         self.bump = bump;
         self.vault_bump = vault_bump;
-        self.status = LaunchPadTokenStatus::TradingEnabled;
+        match trading_starts_at {
+            Some(trading_starts_at) => {
+                require!(
+                    trading_starts_at > self.created_at,
+                    LaunchPadErrorCode::InvalidTradingStartTime
+                );
+                self.trading_starts_at = trading_starts_at;
+                self.status = LaunchPadTokenStatus::Scheduled;
+            }
+            None => {
+                self.trading_starts_at = self.created_at;
+                self.status = LaunchPadTokenStatus::TradingEnabled;
+            }
+        }
+        if let Some(allowlist) = allowlist {
+            require!(
+                allowlist.merkle_root != [0; 32] && allowlist.ends_at > self.trading_starts_at,
+                LaunchPadErrorCode::InvalidAllowlist
+            );
+            self.allowlist_root = allowlist.merkle_root;
+            self.allowlist_ends_at = allowlist.ends_at;
+        }
 
         emit!(LaunchPadTokenCreated {
             creator: self.creator,
             mint: self.mint,
+            quote_mint: self.quote_mint,
+            curve_type: self.curve_type,
+            token_supply: self.token_supply,
+            decimals: self.decimals,
+            graduation_token_amount: self.graduation_token_amount,
+            trading_starts_at: self.trading_starts_at,
+            allowlist_root: self.allowlist_root,
+            allowlist_ends_at: self.allowlist_ends_at,
             status: self.status,
             timestamp: self.created_at,
         });
@@ -89,6 +162,381 @@ impl LaunchPadToken {
         Ok(())
     }
 
+    /// Fills the fields appended after the first release once `migrate_launch_pad_token` has
+    /// grown the account. Those launches all had the fixed supply, 9 decimals and SOL quote.
+    pub fn migrate(&mut self) -> Result<()> {
+        self.quote_mint = NATIVE_MINT;
+        self.token_supply = TOKEN_TOTAL_SUPPLY as u64;
+        self.decimals = LEGACY_TOKEN_DECIMALS;
+        self.graduation_token_amount = TOKEN_GRADUATION_AMOUNT as u64;
+        self.trading_starts_at = self.created_at;
+        Ok(())
+    }
+
+    /// Whether the token can graduate now, graduated tokens and a paused protocol fail.
+    pub fn ready_to_graduate(&self, config: &LaunchPadConfig) -> Result<bool> {
+        config.require_active()?;
+        require!(
+            self.status != LaunchPadTokenStatus::Graduated,
+            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled
+        );
+        Ok(self.status == LaunchPadTokenStatus::ReadyToGraduate)
+    }
+
+    /// Holds back sells from the creator's wallet until `creator_sell_delay` seconds after the
+    /// token was created. The lock follows the wallet, not the tokens: a creator can still move
+    /// their tokens to another wallet and sell from there.
+    pub fn require_creator_sell_delay_met(
+        &self,
+        seller: Pubkey,
+        creator_sell_delay: u64,
+    ) -> Result<()> {
+        if seller != self.creator {
+            return Ok(());
+        }
+        let sell_unlocked_at = self
+            .created_at
+            .checked_add(creator_sell_delay as i64)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        require!(
+            Clock::get()?.unix_timestamp >= sell_unlocked_at,
+            LaunchPadErrorCode::CreatorSellDelayNotMet
+        );
+        Ok(())
+    }
+
+    /// Whether the curve is priced in native SOL rather than an SPL quote mint.
+    pub fn is_native_quote(&self) -> bool {
+        self.quote_mint == NATIVE_MINT
+    }
+
+    pub fn require_trading_enabled(&self) -> Result<()> {
+        if self.status == LaunchPadTokenStatus::Scheduled {
+            require!(
+                Clock::get()?.unix_timestamp >= self.trading_starts_at,
+                LaunchPadErrorCode::TradingNotStarted
+            );
+            return Ok(());
+        }
+        require!(
+            self.status == LaunchPadTokenStatus::TradingEnabled,
+            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled
+        );
+        Ok(())
+    }
+
+    /// Checks that the protocol is active and trading is open, and moves a scheduled launch
+    /// to TradingEnabled once its start time has passed.
+    pub fn open_trading(&mut self, config: &LaunchPadConfig) -> Result<()> {
+        config.require_active()?;
+        self.require_trading_enabled()?;
+        if self.status == LaunchPadTokenStatus::Scheduled {
+            self.update_status(LaunchPadTokenStatus::TradingEnabled)?;
+        }
+        Ok(())
+    }
+
+    /// The creator dev-buy only runs on launches that open for everyone at creation: a
+    /// scheduled, auctioned or allowlisted launch would let it front-run those gates.
+    pub fn require_initial_buy_allowed(&self) -> Result<()> {
+        require!(
+            self.status == LaunchPadTokenStatus::TradingEnabled && self.allowlist_root == [0; 32],
+            LaunchPadErrorCode::InitialBuyNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Holds trading back for a batch auction, only immediate native SOL launches qualify.
+    /// The settlement fills every bid in one curve buy, so launches gated per wallet by an
+    /// allowlist or the anti-snipe limit cannot be auctioned.
+    pub fn start_auction(&mut self, config: &LaunchPadConfig) -> Result<()> {
+        require!(
+            self.is_native_quote(),
+            LaunchPadErrorCode::AuctionRequiresNativeQuote
+        );
+        require!(
+            self.status == LaunchPadTokenStatus::TradingEnabled,
+            LaunchPadErrorCode::InvalidAuctionSchedule
+        );
+        require!(
+            self.allowlist_root == [0; 32] && config.anti_snipe.window == 0,
+            LaunchPadErrorCode::AuctionNotAllowed
+        );
+        self.update_status(LaunchPadTokenStatus::Auction)
+    }
+
+    /// Opens trading once the auction is settled, launch windows count from this moment.
+    pub fn end_auction(&mut self) -> Result<()> {
+        self.trading_starts_at = Clock::get()?.unix_timestamp;
+        if self.status == LaunchPadTokenStatus::Auction {
+            self.update_status(LaunchPadTokenStatus::TradingEnabled)?;
+        }
+        Ok(())
+    }
+
+    /// Whether buys are still limited to the allowlist.
+    pub fn is_allowlist_active(&self) -> Result<bool> {
+        Ok(self.allowlist_root != [0; 32] && Clock::get()?.unix_timestamp < self.allowlist_ends_at)
+    }
+
+    /// Checks that `buyer` is on the allowlist with the allocation in `proof`.
+    pub fn verify_allowlist_proof(&self, buyer: &Pubkey, proof: &AllowlistProof) -> Result<()> {
+        require!(
+            verify_allowlist_proof(
+                &self.allowlist_root,
+                allowlist_leaf(buyer, proof.allocation),
+                &proof.proof,
+            ),
+            LaunchPadErrorCode::InvalidAllowlistProof
+        );
+        Ok(())
+    }
+
+    /// Tokens the curve can still sell before reaching the graduation allocation.
+    pub fn remaining_token_amount(&self) -> Result<u64> {
+        let remaining_token_amount = self
+            .virtual_token_amount
+            .checked_sub(self.graduation_token_amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok(remaining_token_amount)
+    }
+
+    pub fn curve_reserves(&self) -> CurveReserves {
+        CurveReserves {
+            token_supply: self.token_supply as u128,
+            virtual_token_reserve: self.virtual_token_amount as u128,
+            virtual_asset_reserve: self.virtual_asset_amount as u128,
+            current_k: self.current_k,
+        }
+    }
+
+    pub fn quote_buy(
+        &self,
+        config: &LaunchPadConfig,
+        amount: u64,
+        strict_fill: bool,
+    ) -> Result<TradeQuote> {
+        self.quote_buy_at_rate(config, amount, strict_fill, self.buy_fee_rate(config)?)
+    }
+
+    /// Quotes a buy at the base buy fee, skipping the launch fee schedule. Used for fills
+    /// made when the launch opens: the creator dev-buy and the auction settlement.
+    pub fn quote_buy_at_base_fee(
+        &self,
+        config: &LaunchPadConfig,
+        amount: u64,
+        strict_fill: bool,
+    ) -> Result<TradeQuote> {
+        self.quote_buy_at_rate(config, amount, strict_fill, config.protocol_buy_fee)
+    }
+
+    fn quote_buy_at_rate(
+        &self,
+        config: &LaunchPadConfig,
+        amount: u64,
+        strict_fill: bool,
+        buy_fee_rate: u32,
+    ) -> Result<TradeQuote> {
+        let mut buy_fee = calculate_fee(amount, buy_fee_rate)?;
+        let mut asset_amount = amount
+            .checked_sub(buy_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+
+        let reserves = self.curve_reserves();
+        let mut token_amount_out = self.curve_type.token_amount_out(asset_amount, &reserves)?;
+
+        let remaining_token_amount = self.remaining_token_amount()?;
+        if token_amount_out > remaining_token_amount && !strict_fill {
+            // Clamp to the graduation cap and only charge what that fill costs.
+            token_amount_out = remaining_token_amount;
+            let asset_amount_in = self
+                .curve_type
+                .asset_amount_in(token_amount_out, &reserves)?;
+            let amount_with_fee = calculate_amount_with_fee(asset_amount_in, buy_fee_rate)?;
+            // Never charge more than the buyer sent for the smaller fill.
+            require!(
+                amount_with_fee <= amount,
+                LaunchPadErrorCode::SlippageExceeded
+            );
+            buy_fee = calculate_fee(amount_with_fee, buy_fee_rate)?;
+            asset_amount = amount_with_fee
+                .checked_sub(buy_fee)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        }
+
+        require!(
+            token_amount_out <= remaining_token_amount,
+            LaunchPadErrorCode::InsufficientTokenLiquidity
+        );
+
+        self.buy_quote(config, asset_amount, buy_fee, token_amount_out)
+    }
+
+    pub fn quote_buy_exact_out(
+        &self,
+        config: &LaunchPadConfig,
+        token_amount_out: u64,
+    ) -> Result<TradeQuote> {
+        require!(
+            token_amount_out <= self.remaining_token_amount()?,
+            LaunchPadErrorCode::InsufficientTokenLiquidity
+        );
+
+        let asset_amount_in = self
+            .curve_type
+            .asset_amount_in(token_amount_out, &self.curve_reserves())?;
+
+        let buy_fee_rate = self.buy_fee_rate(config)?;
+        let amount_with_fee = calculate_amount_with_fee(asset_amount_in, buy_fee_rate)?;
+        let buy_fee = calculate_fee(amount_with_fee, buy_fee_rate)?;
+        let asset_amount = amount_with_fee
+            .checked_sub(buy_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+
+        self.buy_quote(config, asset_amount, buy_fee, token_amount_out)
+    }
+
+    /// Buy fee rate right now, raised by the launch fee schedule shortly after trading opens.
+    fn buy_fee_rate(&self, config: &LaunchPadConfig) -> Result<u32> {
+        config.buy_fee_at(self.trading_starts_at, Clock::get()?.unix_timestamp)
+    }
+
+    fn buy_quote(
+        &self,
+        config: &LaunchPadConfig,
+        asset_amount: u64,
+        buy_fee: u64,
+        token_amount_out: u64,
+    ) -> Result<TradeQuote> {
+        let virtual_token_amount = self
+            .virtual_token_amount
+            .checked_sub(token_amount_out)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let virtual_asset_amount = self
+            .virtual_asset_amount
+            .checked_add(asset_amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        let virtual_graduation_amount = self
+            .virtual_graduation_amount
+            .checked_add(asset_amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+
+        let amount_in = asset_amount
+            .checked_add(buy_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        // Anything above the base fee is kept by the protocol, not shared with creators
+        let launch_fee = buy_fee.saturating_sub(config.calculate_buy_fee(amount_in)?);
+
+        Ok(TradeQuote {
+            amount_in,
+            amount_out: token_amount_out,
+            fee: buy_fee,
+            creator_fee: config.calculate_creator_fee(buy_fee - launch_fee)?,
+            launch_fee,
+            virtual_token_amount,
+            virtual_asset_amount,
+            virtual_graduation_amount,
+            ready_to_graduate: virtual_graduation_amount >= config.graduate_threshold
+                || virtual_token_amount <= self.graduation_token_amount,
+        })
+    }
+
+    pub fn quote_sell(&self, config: &LaunchPadConfig, token_amount_in: u64) -> Result<TradeQuote> {
+        let asset_amount_out = self
+            .curve_type
+            .asset_amount_out(token_amount_in, &self.curve_reserves())?;
+
+        let sell_fee = config.calculate_sell_fee(asset_amount_out)?;
+        let asset_amount_out_with_fee = asset_amount_out
+            .checked_sub(sell_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+
+        require!(
+            asset_amount_out <= self.virtual_asset_amount,
+            LaunchPadErrorCode::InsufficientAssetLiquidity
+        );
+
+        Ok(TradeQuote {
+            amount_in: token_amount_in,
+            amount_out: asset_amount_out_with_fee,
+            fee: sell_fee,
+            creator_fee: config.calculate_creator_fee(sell_fee)?,
+            launch_fee: 0,
+            virtual_token_amount: self
+                .virtual_token_amount
+                .checked_add(token_amount_in)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?,
+            virtual_asset_amount: self
+                .virtual_asset_amount
+                .checked_sub(asset_amount_out)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?,
+            virtual_graduation_amount: self
+                .virtual_graduation_amount
+                .checked_sub(asset_amount_out)
+                .ok_or(LaunchPadErrorCode::MathOverflow)?,
+            ready_to_graduate: false,
+        })
+    }
+
+    pub fn apply_trade(&mut self, quote: &TradeQuote) -> Result<()> {
+        self.update_virtual_reserves(quote.virtual_token_amount, quote.virtual_asset_amount)?;
+        self.virtual_graduation_amount = quote.virtual_graduation_amount;
+        self.creator_fees = self
+            .creator_fees
+            .checked_add(quote.creator_fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        if quote.ready_to_graduate {
+            self.update_status(LaunchPadTokenStatus::ReadyToGraduate)?;
+        }
+        Ok(())
+    }
+
+    /// Applies a settled buy by `trader` and emits its trade and creator fee events.
+    pub fn apply_buy(
+        &mut self,
+        quote: &TradeQuote,
+        split: &BuySplit,
+        trader: Pubkey,
+    ) -> Result<()> {
+        self.apply_trade(quote)?;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        emit!(LaunchPadTokenBought {
+            trader,
+            mint: self.mint,
+            asset_amount: split.asset_amount,
+            token_amount: quote.amount_out,
+            fee: quote.fee,
+            virtual_token_amount: self.virtual_token_amount,
+            virtual_asset_amount: self.virtual_asset_amount,
+            virtual_graduation_amount: self.virtual_graduation_amount,
+            status: self.status,
+            timestamp,
+        });
+
+        if split.creator_fee > 0 {
+            emit!(CreatorFeesAccrued {
+                creator: self.creator,
+                mint: self.mint,
+                amount: split.creator_fee,
+                unclaimed_amount: self.creator_fees,
+                timestamp,
+            });
+        }
+        Ok(())
+    }
+
+    /// Resets the accrued creator fees and returns the amount to pay out.
+    pub fn claim_creator_fees(&mut self) -> Result<u64> {
+        require!(
+            self.creator_fees > 0,
+            LaunchPadErrorCode::NoCreatorFeesToClaim
+        );
+        let amount = self.creator_fees;
+        self.creator_fees = 0;
+        Ok(amount)
+    }
+
     pub fn update_virtual_reserves(
         &mut self,
         new_virtual_token_amount: u64,
@@ -119,3 +567,610 @@ impl LaunchPadToken {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        allowlist_merkle_proof, allowlist_merkle_root, test_utils::warp_to, AntiSnipeLimit,
+        FeeDecay, LaunchFeeSchedule, ProtocolStatus, MIN_CREATOR_SELL_DELAY,
+    };
+
+    use super::*;
+
+    fn config() -> LaunchPadConfig {
+        LaunchPadConfig {
+            graduate_threshold: 85_000_000_000,
+            protocol_buy_fee: 5_000,
+            protocol_sell_fee: 7_000,
+            status: ProtocolStatus::Active,
+            ..Default::default()
+        }
+    }
+
+    fn created_token(created_at: i64) -> LaunchPadToken {
+        warp_to(created_at);
+        let mut token = LaunchPadToken::default();
+        token
+            .create(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                NATIVE_MINT,
+                1_000_000_000_000_000_000,
+                9,
+                200_000_000_000_000_000,
+                100_000_000_000,
+                CurveType::ConstantProduct,
+                None,
+                None,
+                255,
+                255,
+            )
+            .unwrap();
+        token
+    }
+
+    #[test]
+    fn test_migrate_first_release_layout() {
+        let (creator, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = LaunchPadToken::DISCRIMINATOR.to_vec();
+        (
+            creator,
+            mint,
+            900_000_000_000_000_000u64,
+            30_000_000_000u64,
+            27_000_000_000_000_000_000_000_000_000u128,
+            2_000_000_000u64,
+            0i64,
+            1_700_000_000i64,
+            LaunchPadTokenStatus::TradingEnabled,
+            254u8,
+            253u8,
+        )
+            .serialize(&mut data)
+            .unwrap();
+        data.resize(
+            LaunchPadToken::DISCRIMINATOR.len() + LaunchPadToken::INIT_SPACE,
+            0,
+        );
+
+        let mut token = LaunchPadToken::try_deserialize(&mut &data[..]).unwrap();
+        token.migrate().unwrap();
+        assert_eq!((token.creator, token.mint), (creator, mint));
+        assert_eq!(token.virtual_token_amount, 900_000_000_000_000_000);
+        assert_eq!(token.virtual_asset_amount, 30_000_000_000);
+        assert_eq!(token.current_k, 27_000_000_000_000_000_000_000_000_000);
+        assert_eq!(token.virtual_graduation_amount, 2_000_000_000);
+        assert_eq!(token.created_at, 1_700_000_000);
+        assert_eq!(token.status, LaunchPadTokenStatus::TradingEnabled);
+        assert_eq!((token.bump, token.vault_bump), (254, 253));
+        assert_eq!(token.quote_mint, NATIVE_MINT);
+        assert_eq!(token.token_supply, TOKEN_TOTAL_SUPPLY as u64);
+        assert_eq!(token.decimals, LEGACY_TOKEN_DECIMALS);
+        assert_eq!(
+            token.graduation_token_amount,
+            TOKEN_GRADUATION_AMOUNT as u64
+        );
+        assert_eq!(token.curve_type, CurveType::ConstantProduct);
+        assert_eq!(token.trading_starts_at, 1_700_000_000);
+        assert_eq!(token.allowlist_root, [0; 32]);
+        token.try_serialize(&mut &mut data[..]).unwrap();
+    }
+
+    #[test]
+    fn test_constant_product_requires_asset_reserve() {
+        warp_to(1_700_000_000);
+        let create = |asset_amount, curve_type| {
+            LaunchPadToken::default().create(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                NATIVE_MINT,
+                1_000_000_000_000_000_000,
+                9,
+                200_000_000_000_000_000,
+                asset_amount,
+                curve_type,
+                None,
+                None,
+                255,
+                255,
+            )
+        };
+        assert_eq!(
+            create(0, CurveType::ConstantProduct).unwrap_err(),
+            LaunchPadErrorCode::InvalidCurveParams.into()
+        );
+        let linear = CurveType::Linear {
+            initial_price: 28_000_000_000,
+            slope: 100_000_000_000,
+        };
+        create(0, linear).unwrap();
+    }
+
+    #[test]
+    fn test_creator_sell_delay_boundary() {
+        let created_at = 1_700_000_000;
+        let token = created_token(created_at);
+        let unlocked_at = created_at + MIN_CREATOR_SELL_DELAY as i64;
+
+        warp_to(unlocked_at - 1);
+        assert_eq!(
+            token
+                .require_creator_sell_delay_met(token.creator, MIN_CREATOR_SELL_DELAY)
+                .unwrap_err(),
+            LaunchPadErrorCode::CreatorSellDelayNotMet.into()
+        );
+
+        warp_to(unlocked_at);
+        token
+            .require_creator_sell_delay_met(token.creator, MIN_CREATOR_SELL_DELAY)
+            .unwrap();
+
+        warp_to(unlocked_at + 1);
+        token
+            .require_creator_sell_delay_met(token.creator, MIN_CREATOR_SELL_DELAY)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_creator_sell_delay_counts_from_creation() {
+        let created_at = 1_700_000_000;
+        warp_to(created_at);
+        let mut token = LaunchPadToken::default();
+        token
+            .create(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                NATIVE_MINT,
+                1_000_000_000_000_000_000,
+                9,
+                200_000_000_000_000_000,
+                100_000_000_000,
+                CurveType::ConstantProduct,
+                Some(created_at + 3_600),
+                None,
+                255,
+                255,
+            )
+            .unwrap();
+        let unlocked_at = created_at + MIN_CREATOR_SELL_DELAY as i64;
+
+        warp_to(unlocked_at - 1);
+        assert_eq!(
+            token
+                .require_creator_sell_delay_met(token.creator, MIN_CREATOR_SELL_DELAY)
+                .unwrap_err(),
+            LaunchPadErrorCode::CreatorSellDelayNotMet.into()
+        );
+
+        warp_to(unlocked_at);
+        assert!(unlocked_at < token.trading_starts_at + MIN_CREATOR_SELL_DELAY as i64);
+        token
+            .require_creator_sell_delay_met(token.creator, MIN_CREATOR_SELL_DELAY)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_scheduled_trading_opens_lazily() {
+        let created_at = 1_700_000_000;
+        let trading_starts_at = created_at + 3_600;
+        let create = |trading_starts_at| {
+            let mut token = LaunchPadToken::default();
+            token
+                .create(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    NATIVE_MINT,
+                    1_000_000_000_000_000_000,
+                    9,
+                    200_000_000_000_000_000,
+                    100_000_000_000,
+                    CurveType::ConstantProduct,
+                    Some(trading_starts_at),
+                    None,
+                    255,
+                    255,
+                )
+                .map(|_| token)
+        };
+
+        warp_to(created_at);
+        assert_eq!(
+            create(created_at).unwrap_err(),
+            LaunchPadErrorCode::InvalidTradingStartTime.into()
+        );
+        let mut token = create(trading_starts_at).unwrap();
+        assert_eq!(token.status, LaunchPadTokenStatus::Scheduled);
+
+        warp_to(trading_starts_at - 1);
+        assert_eq!(
+            token.open_trading(&config()).unwrap_err(),
+            LaunchPadErrorCode::TradingNotStarted.into()
+        );
+        assert_eq!(token.status, LaunchPadTokenStatus::Scheduled);
+
+        warp_to(trading_starts_at);
+        token.require_trading_enabled().unwrap();
+        assert_eq!(token.status, LaunchPadTokenStatus::Scheduled);
+        token.open_trading(&config()).unwrap();
+        assert_eq!(token.status, LaunchPadTokenStatus::TradingEnabled);
+    }
+
+    #[test]
+    fn test_paused_protocol_blocks_buy_and_sell_token() {
+        let mut token = created_token(1_700_000_000);
+        let mut config = config();
+        config.pause().unwrap();
+        assert_eq!(
+            token.open_trading(&config).unwrap_err(),
+            LaunchPadErrorCode::ProtocolConfigNotActive.into()
+        );
+
+        config.unpause().unwrap();
+        token.open_trading(&config).unwrap();
+    }
+
+    #[test]
+    fn test_paused_protocol_blocks_graduation() {
+        let mut token = created_token(1_700_000_000);
+        let mut config = config();
+        assert!(!token.ready_to_graduate(&config).unwrap());
+        token.status = LaunchPadTokenStatus::ReadyToGraduate;
+        assert!(token.ready_to_graduate(&config).unwrap());
+
+        config.pause().unwrap();
+        assert_eq!(
+            token.ready_to_graduate(&config).unwrap_err(),
+            LaunchPadErrorCode::ProtocolConfigNotActive.into()
+        );
+
+        config.unpause().unwrap();
+        token.graduate().unwrap();
+        assert_eq!(
+            token.ready_to_graduate(&config).unwrap_err(),
+            LaunchPadErrorCode::LaunchPadTokenTradingNotEnabled.into()
+        );
+    }
+
+    #[test]
+    fn test_allowlist_presale() {
+        let created_at = 1_700_000_000;
+        let buyers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let leaves = [
+            allowlist_leaf(&buyers[0], 1_000),
+            allowlist_leaf(&buyers[1], 2_000),
+        ];
+        let allowlist = |ends_at| Allowlist {
+            merkle_root: allowlist_merkle_root(&leaves),
+            ends_at,
+        };
+        let create = |allowlist| {
+            let mut token = LaunchPadToken::default();
+            token
+                .create(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    NATIVE_MINT,
+                    1_000_000_000_000_000_000,
+                    9,
+                    200_000_000_000_000_000,
+                    100_000_000_000,
+                    CurveType::ConstantProduct,
+                    None,
+                    Some(allowlist),
+                    255,
+                    255,
+                )
+                .map(|_| token)
+        };
+
+        warp_to(created_at);
+        assert_eq!(
+            create(allowlist(created_at)).unwrap_err(),
+            LaunchPadErrorCode::InvalidAllowlist.into()
+        );
+        let token = create(allowlist(created_at + 600)).unwrap();
+        assert!(token.is_allowlist_active().unwrap());
+
+        let proof = AllowlistProof {
+            allocation: 2_000,
+            proof: allowlist_merkle_proof(&leaves, 1),
+        };
+        token.verify_allowlist_proof(&buyers[1], &proof).unwrap();
+        assert_eq!(
+            token
+                .verify_allowlist_proof(&buyers[0], &proof)
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidAllowlistProof.into()
+        );
+
+        warp_to(created_at + 600);
+        assert!(!token.is_allowlist_active().unwrap());
+    }
+
+    #[test]
+    fn test_creator_sell_delay_ignores_other_sellers() {
+        let created_at = 1_700_000_000;
+        let token = created_token(created_at);
+
+        token
+            .require_creator_sell_delay_met(Pubkey::new_unique(), MIN_CREATOR_SELL_DELAY)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_quote_buy_and_sell() {
+        let mut token = created_token(1_700_000_000);
+        let config = config();
+
+        let quote = token.quote_buy(&config, 1_000_000_000, true).unwrap();
+        assert_eq!(quote.amount_in, 1_000_000_000);
+        assert_eq!(quote.amount_out, 9_851_972_869_944_057);
+        assert_eq!(quote.fee, 5_000_000);
+        assert_eq!(quote.virtual_token_amount, 990_148_027_130_055_943);
+        assert_eq!(quote.virtual_asset_amount, 100_995_000_000);
+        assert_eq!(quote.virtual_graduation_amount, 995_000_000);
+        assert!(!quote.ready_to_graduate);
+        token.apply_trade(&quote).unwrap();
+
+        let quote = token.quote_sell(&config, 9_000_000_869_944_000).unwrap();
+        assert_eq!(quote.amount_in, 9_000_000_869_944_000);
+        assert_eq!(quote.amount_out, 903_362_042);
+        assert_eq!(quote.fee, 6_368_111);
+        assert_eq!(quote.virtual_token_amount, 999_148_027_999_999_943);
+        assert_eq!(quote.virtual_asset_amount, 100_085_269_847);
+        assert_eq!(quote.virtual_graduation_amount, 85_269_847);
+        assert!(!quote.ready_to_graduate);
+        token.apply_trade(&quote).unwrap();
+        assert_eq!(token.status, LaunchPadTokenStatus::TradingEnabled);
+    }
+
+    #[test]
+    fn test_quote_buy_over_graduation_cap() {
+        let token = created_token(1_700_000_000);
+        let config = config();
+
+        assert_eq!(
+            token.quote_buy(&config, 450_000_000_000, true).unwrap_err(),
+            LaunchPadErrorCode::InsufficientTokenLiquidity.into()
+        );
+
+        let quote = token.quote_buy(&config, 450_000_000_000, false).unwrap();
+        assert_eq!(quote.amount_in, 402_010_050_252);
+        assert_eq!(quote.amount_out, 800_000_000_000_000_000);
+        assert_eq!(quote.fee, 2_010_050_251);
+        assert_eq!(quote.virtual_token_amount, token.graduation_token_amount);
+        assert_eq!(quote.virtual_asset_amount, 500_000_000_001);
+        assert!(quote.ready_to_graduate);
+
+        // Sending just enough to cross the cap charges no more than was sent.
+        let quote = token.quote_buy(&config, 402_010_050_253, false).unwrap();
+        assert_eq!(quote.amount_in, 402_010_050_252);
+        assert!(quote.ready_to_graduate);
+    }
+
+    #[test]
+    fn test_quote_buy_exact_out() {
+        let token = created_token(1_700_000_000);
+        let config = config();
+
+        let quote = token
+            .quote_buy_exact_out(&config, 9_851_972_869_944_057)
+            .unwrap();
+        assert_eq!(quote.amount_out, 9_851_972_869_944_057);
+        assert_eq!(quote.amount_in, 1_000_000_002);
+        assert_eq!(
+            quote.fee,
+            config.calculate_buy_fee(quote.amount_in).unwrap()
+        );
+
+        assert_eq!(
+            token
+                .quote_buy_exact_out(&config, 800_000_000_000_000_001)
+                .unwrap_err(),
+            LaunchPadErrorCode::InsufficientTokenLiquidity.into()
+        );
+    }
+
+    #[test]
+    fn test_creator_fees_accrue_and_claim() {
+        let mut token = created_token(1_700_000_000);
+        let config = LaunchPadConfig {
+            creator_fee_bps: 200_000,
+            ..config()
+        };
+
+        let quote = token.quote_buy(&config, 1_000_000_000, true).unwrap();
+        assert_eq!(quote.fee, 5_000_000);
+        assert_eq!(quote.creator_fee, 1_000_000);
+        token.apply_trade(&quote).unwrap();
+
+        let quote = token.quote_sell(&config, 9_000_000_869_944_000).unwrap();
+        assert_eq!(quote.fee, 6_368_111);
+        assert_eq!(quote.creator_fee, 1_273_622);
+        token.apply_trade(&quote).unwrap();
+        assert_eq!(token.creator_fees, 2_273_622);
+
+        assert_eq!(token.claim_creator_fees().unwrap(), 2_273_622);
+        assert_eq!(token.creator_fees, 0);
+        assert_eq!(
+            token.claim_creator_fees().unwrap_err(),
+            LaunchPadErrorCode::NoCreatorFeesToClaim.into()
+        );
+    }
+
+    #[test]
+    fn test_launch_fee_decays_to_base_fee() {
+        let token = created_token(1_700_000_000);
+        let config = LaunchPadConfig {
+            creator_fee_bps: 200_000,
+            launch_fee: LaunchFeeSchedule {
+                start_fee: 500_000,
+                window: 60,
+                decay: FeeDecay::Linear,
+            },
+            ..config()
+        };
+
+        let quote = token.quote_buy(&config, 1_000_000_000, true).unwrap();
+        assert_eq!(quote.fee, 500_000_000);
+        assert_eq!(quote.launch_fee, 495_000_000);
+        // creators only share the base fee
+        assert_eq!(quote.creator_fee, 1_000_000);
+
+        warp_to(1_700_000_060);
+        let quote = token.quote_buy(&config, 1_000_000_000, true).unwrap();
+        assert_eq!(quote.fee, 5_000_000);
+        assert_eq!(quote.launch_fee, 0);
+        assert_eq!(quote.amount_out, 9_851_972_869_944_057);
+    }
+
+    #[test]
+    fn test_initial_buy_only_on_immediate_launches() {
+        let created_at = 1_700_000_000;
+        let create = |trading_starts_at, allowlist| {
+            let mut token = LaunchPadToken::default();
+            token
+                .create(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    NATIVE_MINT,
+                    1_000_000_000_000_000_000,
+                    9,
+                    200_000_000_000_000_000,
+                    100_000_000_000,
+                    CurveType::ConstantProduct,
+                    trading_starts_at,
+                    allowlist,
+                    255,
+                    255,
+                )
+                .map(|_| token)
+        };
+
+        warp_to(created_at);
+        let mut token = create(None, None).unwrap();
+        token.require_initial_buy_allowed().unwrap();
+
+        token.start_auction(&config()).unwrap();
+        assert_eq!(
+            token.require_initial_buy_allowed().unwrap_err(),
+            LaunchPadErrorCode::InitialBuyNotAllowed.into()
+        );
+
+        let token = create(Some(created_at + 3_600), None).unwrap();
+        assert_eq!(
+            token.require_initial_buy_allowed().unwrap_err(),
+            LaunchPadErrorCode::InitialBuyNotAllowed.into()
+        );
+
+        let allowlist = Allowlist {
+            merkle_root: [1; 32],
+            ends_at: created_at + 3_600,
+        };
+        let token = create(None, Some(allowlist)).unwrap();
+        assert_eq!(
+            token.require_initial_buy_allowed().unwrap_err(),
+            LaunchPadErrorCode::InitialBuyNotAllowed.into()
+        );
+    }
+
+    #[test]
+    fn test_base_fee_quote_skips_launch_fee() {
+        let token = created_token(1_700_000_000);
+        let config = LaunchPadConfig {
+            launch_fee: LaunchFeeSchedule {
+                start_fee: 500_000,
+                window: 60,
+                decay: FeeDecay::Linear,
+            },
+            ..config()
+        };
+
+        let quote = token
+            .quote_buy_at_base_fee(&config, 1_000_000_000, true)
+            .unwrap();
+        assert_eq!(quote.fee, 5_000_000);
+        assert_eq!(quote.launch_fee, 0);
+        assert_eq!(quote.amount_out, 9_851_972_869_944_057);
+        assert!(
+            token
+                .quote_buy(&config, 1_000_000_000, true)
+                .unwrap()
+                .launch_fee
+                > 0
+        );
+    }
+
+    #[test]
+    fn test_auction_rejects_per_wallet_gates() {
+        let created_at = 1_700_000_000;
+        warp_to(created_at);
+        let mut token = created_token(created_at);
+        let anti_snipe = LaunchPadConfig {
+            anti_snipe: AntiSnipeLimit {
+                max_wallet_share: 10_000,
+                window: 60,
+            },
+            ..config()
+        };
+        assert_eq!(
+            token.start_auction(&anti_snipe).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotAllowed.into()
+        );
+
+        let mut token = LaunchPadToken::default();
+        token
+            .create(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                NATIVE_MINT,
+                1_000_000_000_000_000_000,
+                9,
+                200_000_000_000_000_000,
+                100_000_000_000,
+                CurveType::ConstantProduct,
+                None,
+                Some(Allowlist {
+                    merkle_root: [1; 32],
+                    ends_at: created_at + 3_600,
+                }),
+                255,
+                255,
+            )
+            .unwrap();
+        assert_eq!(
+            token.start_auction(&config()).unwrap_err(),
+            LaunchPadErrorCode::AuctionNotAllowed.into()
+        );
+        assert_eq!(token.status, LaunchPadTokenStatus::TradingEnabled);
+    }
+
+    #[test]
+    fn test_auction_settles_at_base_fee() {
+        let created_at = 1_700_000_000;
+        let mut token = created_token(created_at);
+        let config = LaunchPadConfig {
+            launch_fee: LaunchFeeSchedule {
+                start_fee: 500_000,
+                window: 60,
+                decay: FeeDecay::Linear,
+            },
+            ..config()
+        };
+        token.start_auction(&config).unwrap();
+
+        // settlement runs while the launch fee would still be at its peak
+        warp_to(created_at + 1_200);
+        token.end_auction().unwrap();
+        assert_eq!(token.trading_starts_at, created_at + 1_200);
+        let quote = token
+            .quote_buy_at_base_fee(&config, 1_000_000_000, false)
+            .unwrap();
+        assert_eq!(quote.fee, 5_000_000);
+        assert_eq!(quote.launch_fee, 0);
+        assert_eq!(
+            token.quote_buy(&config, 1_000_000_000, false).unwrap().fee,
+            500_000_000
+        );
+    }
+}
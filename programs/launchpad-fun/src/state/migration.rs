@@ -0,0 +1,52 @@
+#![allow(deprecated)]
+use anchor_lang::prelude::*;
+
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::LaunchPadErrorCode;
+
+/// Grows an account written by an earlier layout to `space` bytes, the payer covers the extra
+/// rent, and rewrites it after `migrate` has filled the appended fields. The appended bytes
+/// are zeroed, so the account deserializes with default values for every new field.
+pub fn migrate_account<'info, T>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    migrate: impl FnOnce(&mut T) -> Result<()>,
+) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize,
+{
+    require_keys_eq!(
+        *account.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    require!(
+        account.data_len() < space,
+        LaunchPadErrorCode::AccountAlreadyMigrated
+    );
+
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let lamports = rent_exempt.saturating_sub(account.lamports());
+    if lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+    account.realloc(space, true)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    let mut migrated = T::try_deserialize(&mut &data[..])?;
+    migrate(&mut migrated)?;
+    migrated.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
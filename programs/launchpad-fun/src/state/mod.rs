@@ -1,7 +1,19 @@
+pub mod auction;
+pub mod auction_bid;
+pub mod buyer_record;
 pub mod constants;
 pub mod launch_pad_config;
 pub mod launch_pad_token;
+pub mod migration;
+pub mod referral;
+pub mod vesting;
 
+pub use auction::*;
+pub use auction_bid::*;
+pub use buyer_record::*;
 pub use constants::*;
 pub use launch_pad_config::*;
 pub use launch_pad_token::*;
+pub use migration::*;
+pub use referral::*;
+pub use vesting::*;
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::{account, prelude::Pubkey, InitSpace};
+
+use crate::{LaunchPadErrorCode, DISC_REFERRAL_ACCOUNT};
+
+#[derive(Default, Debug, InitSpace)]
+#[account(discriminator = DISC_REFERRAL_ACCOUNT)]
+pub struct Referral {
+    /// The partner credited with referred trades
+    pub referrer: Pubkey,
+    /// The quote mint the referral fees are paid in, the wSOL mint for native SOL
+    pub quote_mint: Pubkey,
+    /// Referral fees accrued and not yet withdrawn
+    pub unclaimed_fees: u64,
+    /// Total referral fees accrued since the referral was registered
+    pub lifetime_fees: u64,
+    /// Total quote volume of the referred trades
+    pub lifetime_volume: u64,
+    /// The number of referred trades
+    pub trade_count: u64,
+    /// The bump seed for the PDA
+    pub bump: u8,
+}
+
+impl Referral {
+    pub const SEED: &'static [u8] = b"referral:";
+
+    pub fn init(&mut self, referrer: Pubkey, quote_mint: Pubkey, bump: u8) -> Result<()> {
+        require!(
+            referrer != Pubkey::default(),
+            LaunchPadErrorCode::InvalidReferrer
+        );
+        self.referrer = referrer;
+        self.quote_mint = quote_mint;
+        self.bump = bump;
+        Ok(())
+    }
+
+    pub fn require_not_self_referral(&self, trader: Pubkey) -> Result<()> {
+        require!(self.referrer != trader, LaunchPadErrorCode::SelfReferral);
+        Ok(())
+    }
+
+    pub fn accrue(&mut self, volume: u64, fee: u64) -> Result<()> {
+        self.unclaimed_fees = self
+            .unclaimed_fees
+            .checked_add(fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        self.lifetime_fees = self
+            .lifetime_fees
+            .checked_add(fee)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        self.lifetime_volume = self
+            .lifetime_volume
+            .checked_add(volume)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        self.trade_count = self
+            .trade_count
+            .checked_add(1)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Resets the unclaimed referral fees and returns the amount to pay out.
+    pub fn withdraw(&mut self) -> Result<u64> {
+        require!(
+            self.unclaimed_fees > 0,
+            LaunchPadErrorCode::NoReferralFeesToWithdraw
+        );
+        let amount = self.unclaimed_fees;
+        self.unclaimed_fees = 0;
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::NATIVE_MINT;
+
+    use super::*;
+
+    #[test]
+    fn test_accrue_and_withdraw() {
+        let referrer = Pubkey::new_unique();
+        let mut referral = Referral::default();
+        referral.init(referrer, NATIVE_MINT, 255).unwrap();
+
+        assert_eq!(
+            referral.require_not_self_referral(referrer).unwrap_err(),
+            LaunchPadErrorCode::SelfReferral.into()
+        );
+        assert!(referral
+            .require_not_self_referral(Pubkey::new_unique())
+            .is_ok());
+
+        referral.accrue(1_000_000_000, 400_000).unwrap();
+        referral.accrue(903_362_042, 509_448).unwrap();
+        assert_eq!(referral.unclaimed_fees, 909_448);
+        assert_eq!(referral.lifetime_volume, 1_903_362_042);
+        assert_eq!(referral.trade_count, 2);
+
+        assert_eq!(referral.withdraw().unwrap(), 909_448);
+        assert_eq!(referral.unclaimed_fees, 0);
+        assert_eq!(referral.lifetime_fees, 909_448);
+        assert_eq!(
+            referral.withdraw().unwrap_err(),
+            LaunchPadErrorCode::NoReferralFeesToWithdraw.into()
+        );
+    }
+
+    #[test]
+    fn test_init_rejects_default_referrer() {
+        assert_eq!(
+            Referral::default()
+                .init(Pubkey::default(), NATIVE_MINT, 255)
+                .unwrap_err(),
+            LaunchPadErrorCode::InvalidReferrer.into()
+        );
+    }
+}
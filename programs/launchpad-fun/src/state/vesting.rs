@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::{account, prelude::Pubkey, InitSpace};
+
+use crate::{LaunchPadErrorCode, DISC_VESTING_ACCOUNT};
+
+/// Team allocation requested at `create_token`.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct VestingSchedule {
+    /// Tokens minted on top of the curve supply, in parts per 1_000_000 of the curve supply
+    pub share: u32,
+    /// Seconds after graduation before anything vests
+    pub cliff: u64,
+    /// Seconds after graduation until everything has vested
+    pub duration: u64,
+}
+
+#[derive(Default, Debug, InitSpace)]
+#[account(discriminator = DISC_VESTING_ACCOUNT)]
+pub struct Vesting {
+    /// The mint of the launch pad token
+    pub mint: Pubkey,
+    /// The creator the team allocation vests to
+    pub creator: Pubkey,
+    /// Tokens held for the team allocation
+    pub total_amount: u64,
+    /// Tokens already claimed by the creator
+    pub claimed_amount: u64,
+    /// Seconds after graduation before anything vests
+    pub cliff: u64,
+    /// Seconds after graduation until everything has vested
+    pub duration: u64,
+    /// The bump seed for the PDA
+    pub bump: u8,
+}
+
+impl Vesting {
+    pub const SEED: &'static [u8] = b"vesting:";
+
+    pub fn init(
+        &mut self,
+        mint: Pubkey,
+        creator: Pubkey,
+        total_amount: u64,
+        schedule: VestingSchedule,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            total_amount > 0 && schedule.duration > 0 && schedule.cliff <= schedule.duration,
+            LaunchPadErrorCode::InvalidVestingSchedule
+        );
+        self.mint = mint;
+        self.creator = creator;
+        self.total_amount = total_amount;
+        self.cliff = schedule.cliff;
+        self.duration = schedule.duration;
+        self.bump = bump;
+        Ok(())
+    }
+
+    /// Tokens vested at `now` for a token graduated at `graduated_at`, released linearly over
+    /// the duration once the cliff has passed.
+    pub fn vested_amount(&self, graduated_at: i64, now: i64) -> Result<u64> {
+        let elapsed = now
+            .checked_sub(graduated_at)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        if elapsed < self.cliff as i64 {
+            return Ok(0);
+        }
+        if elapsed >= self.duration as i64 {
+            return Ok(self.total_amount);
+        }
+        let vested_amount = (self.total_amount as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?
+            .checked_div(self.duration as u128)
+            .ok_or(LaunchPadErrorCode::MathOverflow)? as u64;
+        Ok(vested_amount)
+    }
+
+    /// Marks the tokens vested so far as claimed and returns the amount to pay out. Nothing
+    /// vests before the token graduates so team tokens never reach the curve.
+    pub fn claim(&mut self, graduated_at: i64, now: i64) -> Result<u64> {
+        require!(graduated_at > 0, LaunchPadErrorCode::VestingNotStarted);
+        let amount = self
+            .vested_amount(graduated_at, now)?
+            .checked_sub(self.claimed_amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        require!(amount > 0, LaunchPadErrorCode::NoVestedTokensToClaim);
+        self.claimed_amount = self
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(LaunchPadErrorCode::MathOverflow)?;
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn vesting() -> Vesting {
+        let mut vesting = Vesting::default();
+        vesting
+            .init(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                1_000_000,
+                VestingSchedule {
+                    share: 100_000,
+                    cliff: 100,
+                    duration: 1_000,
+                },
+                254,
+            )
+            .unwrap();
+        vesting
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        for (total_amount, cliff, duration) in [(0, 0, 1_000), (1_000, 0, 0), (1_000, 1_001, 1_000)]
+        {
+            let schedule = VestingSchedule {
+                share: 100_000,
+                cliff,
+                duration,
+            };
+            assert_eq!(
+                Vesting::default()
+                    .init(
+                        Pubkey::new_unique(),
+                        Pubkey::new_unique(),
+                        total_amount,
+                        schedule,
+                        254
+                    )
+                    .unwrap_err(),
+                LaunchPadErrorCode::InvalidVestingSchedule.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cliff_and_linear_release() {
+        let graduated_at = 1_700_000_000;
+        let mut vesting = vesting();
+
+        assert_eq!(
+            vesting.claim(0, graduated_at).unwrap_err(),
+            LaunchPadErrorCode::VestingNotStarted.into()
+        );
+        assert_eq!(
+            vesting.claim(graduated_at, graduated_at + 99).unwrap_err(),
+            LaunchPadErrorCode::NoVestedTokensToClaim.into()
+        );
+
+        assert_eq!(
+            vesting.claim(graduated_at, graduated_at + 100).unwrap(),
+            100_000
+        );
+        assert_eq!(
+            vesting.claim(graduated_at, graduated_at + 500).unwrap(),
+            400_000
+        );
+        assert_eq!(
+            vesting.claim(graduated_at, graduated_at + 500).unwrap_err(),
+            LaunchPadErrorCode::NoVestedTokensToClaim.into()
+        );
+        assert_eq!(
+            vesting.claim(graduated_at, graduated_at + 5_000).unwrap(),
+            500_000
+        );
+        assert_eq!(vesting.claimed_amount, vesting.total_amount);
+    }
+}
@@ -19,4 +19,6 @@ pub enum LaunchPadTokenStatus {
     TradingEnabled,
     ReadyToGraduate,
     Graduated,
+    Scheduled,
+    Auction,
 }
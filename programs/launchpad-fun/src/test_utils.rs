@@ -0,0 +1,35 @@
+use std::{cell::Cell, sync::Once};
+
+use anchor_lang::solana_program::{
+    clock::Clock,
+    entrypoint::SUCCESS,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+};
+
+thread_local! {
+    static UNIX_TIMESTAMP: Cell<i64> = const { Cell::new(0) };
+}
+
+static INIT_STUBS: Once = Once::new();
+
+/// Serves `Clock::get()` off-chain from the timestamp set by `warp_to`.
+struct ClockStubs;
+
+impl SyscallStubs for ClockStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: UNIX_TIMESTAMP.with(Cell::get),
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+}
+
+/// Sets the unix timestamp returned by `Clock::get()` on the current test thread.
+pub fn warp_to(unix_timestamp: i64) {
+    INIT_STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(ClockStubs));
+    });
+    UNIX_TIMESTAMP.with(|timestamp| timestamp.set(unix_timestamp));
+}
@@ -27,6 +27,7 @@ import {
   getBase64EncodedWireTransaction,
 } from "@solana/kit";
 import { expect } from "chai";
+import { createHash, randomBytes } from "crypto";
 import * as programClient from "../clients/js/src/generated";
 import { getSetComputeUnitLimitInstruction } from "@solana-program/compute-budget";
 import {
@@ -40,6 +41,11 @@ import {
   NATIVE_MINT,
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  getTokenMetadata,
+  mintTo,
 } from "@solana/spl-token";
 import { findAssociatedTokenPda } from "@solana-program/token";
 
@@ -47,6 +53,7 @@ type TestEnvironment = {
   rpcClient: RpcClient;
   authority: TransactionSigner;
   creator: TransactionSigner;
+  investor: TransactionSigner;
   mint: TransactionSigner;
   programClient: typeof programClient;
 };
@@ -61,6 +68,10 @@ const createTestEnvironment = async (): Promise<TestEnvironment> => {
     rpcClient,
     10_000_000_000n
   );
+  const investor = await generateKeyPairSignerWithSol(
+    rpcClient,
+    10_000_000_000n
+  );
   let mint = await generateKeyPairSigner();
   // while (true) {
   //   const tokenArray = [NATIVE_MINT, new anchor.web3.PublicKey(mint.address)];
@@ -75,7 +86,7 @@ const createTestEnvironment = async (): Promise<TestEnvironment> => {
   //   mint = await generateKeyPairSigner();
   // }
 
-  return { rpcClient, authority, creator, mint, programClient };
+  return { rpcClient, authority, creator, investor, mint, programClient };
 };
 
 type RpcClient = {
@@ -224,6 +235,145 @@ const displaySolanaError = (e: any) => {
   }
 };
 
+const getGraduateToRaydiumInstruction = async (
+  testEnv: TestEnvironment,
+  mint: Address = testEnv.mint.address,
+  mintTokenProgram: anchor.web3.PublicKey = TOKEN_2022_PROGRAM_ID
+) => {
+  const { programClient: program, creator } = testEnv;
+  const codec = getBase58Encoder();
+
+  // const raydiumCpmmProgramId = new anchor.web3.PublicKey(
+  //   "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C"
+  // );
+  // const ammConfigPda = new anchor.web3.PublicKey(
+  //   "D4FPEruKEHrG5TenZ2mpDGEfu1iUvTiqBxvpU8HLBvC2"
+  // );
+
+  const raydiumCpmmProgramId = new anchor.web3.PublicKey(
+    "DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb"
+  );
+  const ammConfigPda = new anchor.web3.PublicKey(
+    "A9qBhPy4k5UYW72hSgAkh1Epr2do69P54yzzcMV3yv6b"
+  );
+
+  const tokenArray = [NATIVE_MINT, new anchor.web3.PublicKey(mint)];
+  tokenArray.sort((a, b) => {
+    const bufferA = a.toBuffer();
+    const bufferB = b.toBuffer();
+    return Buffer.compare(bufferA, bufferB);
+  });
+  const token0Mint = tokenArray[0];
+  const token1Mint = tokenArray[1];
+
+  const [poolStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pool"),
+      ammConfigPda.toBuffer(),
+      token0Mint.toBuffer(),
+      token1Mint.toBuffer(),
+    ],
+    raydiumCpmmProgramId
+  );
+
+  const [token0VaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_vault"), poolStatePda.toBuffer(), token0Mint.toBuffer()],
+    raydiumCpmmProgramId
+  );
+
+  const [token1VaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_vault"), poolStatePda.toBuffer(), token1Mint.toBuffer()],
+    raydiumCpmmProgramId
+  );
+  const [lpMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_lp_mint"), poolStatePda.toBuffer()],
+    raydiumCpmmProgramId
+  );
+
+  const creatorAddressBytes = codec.encode(creator.address.toString());
+  const [lpTokenPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(creatorAddressBytes), // owner
+      TOKEN_PROGRAM_ID.toBuffer(),
+      lpMintPda.toBuffer(),
+    ],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  const graduateIx = await program.getGraduateToRaydiumInstructionAsync({
+    investor: creator,
+    ammConfig: ammConfigPda.toBase58() as Address,
+    poolState: poolStatePda.toBase58() as Address,
+    lpToken: lpTokenPda.toBase58() as Address,
+    token0Vault: token0VaultPda.toBase58() as Address,
+    token1Vault: token1VaultPda.toBase58() as Address,
+    mint,
+    quoteMint: NATIVE_MINT.toBase58() as Address,
+    mintTokenProgram: mintTokenProgram.toBase58() as Address,
+    quoteTokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+  });
+
+  return { graduateIx, token0VaultPda, token1VaultPda, lpTokenPda };
+};
+
+const expectLaunchPadError = async (
+  testEnv: TestEnvironment,
+  instructions: any[],
+  code: programClient.LaunchpadFunError
+) => {
+  const transactionMessage = pipe(
+    await createDefaultTransaction(testEnv),
+    (tx) => appendTransactionMessageInstructions(instructions, tx)
+  );
+  try {
+    await signAndSendTransaction(
+      testEnv.rpcClient,
+      transactionMessage,
+      "confirmed",
+      false
+    );
+  } catch (e: any) {
+    expect(
+      programClient.isLaunchpadFunError(e?.cause, transactionMessage, code)
+    ).to.equal(true);
+    return;
+  }
+  expect.fail("transaction should have failed");
+};
+
+const simulateQuote = async (testEnv: TestEnvironment, instruction: any) => {
+  const transactionMessage = pipe(
+    await createDefaultTransaction(testEnv),
+    (tx) => appendTransactionMessageInstructions([instruction], tx)
+  );
+  const signed = await signTransactionMessageWithSigners(transactionMessage);
+  const sim = await testEnv.rpcClient.rpc
+    .simulateTransaction(getBase64EncodedWireTransaction(signed), {
+      encoding: "base64",
+    })
+    .send();
+
+  expect(sim.value.err).to.be.null;
+  const [data] = sim.value.returnData.data;
+  return testEnv.programClient
+    .getTradeQuoteDecoder()
+    .decode(Buffer.from(data, "base64"));
+};
+
+const findLaunchPadTokenPda = (testEnv: TestEnvironment) => {
+  const programId = new anchor.web3.PublicKey(
+    testEnv.programClient.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+  );
+  const mintAddressBytes = getBase58Encoder().encode(
+    testEnv.mint.address.toString()
+  );
+  const [launchPadTokenPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("launch_pad_token:"), Buffer.from(mintAddressBytes)],
+    programId
+  );
+  return launchPadTokenPda.toBase58() as Address;
+};
+
 describe("Launch Pad Fun", () => {
   let testEnv: TestEnvironment;
 
@@ -346,12 +496,23 @@ describe("Launch Pad Fun", () => {
       name: "New Meme Token",
       symbol: "NMT",
       uri: "https://example.com/nmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
     } as CreateTokenInstructionDataArgs;
 
     // call initialize
     const ix = await program.getCreateTokenInstructionAsync({
       creator: creator,
       mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
       ...args,
     });
 
@@ -400,8 +561,36 @@ describe("Launch Pad Fun", () => {
     expect(launchPadTokenAccount.value.amount).to.equal("1000000000000000000");
   });
 
+  it("quotes a buy without changing state", async () => {
+    const { programClient: program } = testEnv;
+    const launchPadToken = findLaunchPadTokenPda(testEnv);
+
+    const quoteIx = await program.getQuoteBuyInstructionAsync({
+      launchPadToken,
+      amount: 1_000_000_000n,
+      strictFill: false,
+    });
+    const quote = await simulateQuote(testEnv, quoteIx);
+
+    expect(quote.amountIn).to.equal(1_000_000_000n);
+    expect(quote.amountOut).to.equal(9_851_972_869_944_057n);
+    expect(quote.fee).to.equal(5_000_000n);
+    expect(quote.virtualTokenAmount).to.equal(990148027130055943n);
+    expect(quote.virtualAssetAmount).to.equal(100995000000n);
+    expect(quote.virtualGraduationAmount).to.equal(995000000n);
+    expect(quote.readyToGraduate).to.equal(false);
+
+    const token = await program.fetchLaunchPadToken(
+      testEnv.rpcClient.rpc,
+      launchPadToken,
+      { commitment: "confirmed" }
+    );
+    expect(token.data.virtualAssetAmount).to.equal(100_000_000_000n);
+  });
+
   it("buys a token", async () => {
-    const { rpcClient, programClient: program, creator, mint } = testEnv;
+    const { rpcClient, programClient: program, creator, investor, mint } =
+      testEnv;
     const programId = new anchor.web3.PublicKey(
       program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
     );
@@ -440,7 +629,7 @@ describe("Launch Pad Fun", () => {
 
     const [investorTokenAccountPda] = await findAssociatedTokenPda({
       /** The wallet address of the associated token account. */
-      owner: creator.address,
+      owner: investor.address,
       /** The address of the token program to use. */
       tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
       /** The mint address of the associated token account. */
@@ -450,11 +639,15 @@ describe("Launch Pad Fun", () => {
     // prepare args
     const args = {
       amount: 1_000_000_000n,
+      minTokenOut: 9_851_972_869_944_057n,
+      strictFill: false,
+      allowlistProof: null,
     } as BuyTokenInstructionDataArgs;
 
     const ix = await program.getBuyTokenInstructionAsync({
-      investor: creator,
+      investor: investor,
       mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
       ...args,
     });
 
@@ -516,8 +709,25 @@ describe("Launch Pad Fun", () => {
     expect(investorTokenAccount.value.amount).to.equal("9851972869944057"); //9.851.972,869944057
   });
 
+  it("quotes a sell matching the executed trade", async () => {
+    const { programClient: program } = testEnv;
+
+    const quoteIx = await program.getQuoteSellInstructionAsync({
+      launchPadToken: findLaunchPadTokenPda(testEnv),
+      amount: 9_000_000_869_944_000n,
+    });
+    const quote = await simulateQuote(testEnv, quoteIx);
+
+    expect(quote.amountIn).to.equal(9_000_000_869_944_000n);
+    expect(quote.amountOut).to.equal(903_362_042n);
+    expect(quote.fee).to.equal(6_368_111n);
+    expect(quote.virtualGraduationAmount).to.equal(85269847n);
+    expect(quote.readyToGraduate).to.equal(false);
+  });
+
   it("sells a token", async () => {
-    const { rpcClient, programClient: program, creator, mint } = testEnv;
+    const { rpcClient, programClient: program, creator, investor, mint } =
+      testEnv;
     const programId = new anchor.web3.PublicKey(
       program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
     );
@@ -556,7 +766,7 @@ describe("Launch Pad Fun", () => {
 
     const [investorTokenAccountPda] = await findAssociatedTokenPda({
       /** The wallet address of the associated token account. */
-      owner: creator.address,
+      owner: investor.address,
       /** The address of the token program to use. */
       tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
       /** The mint address of the associated token account. */
@@ -566,11 +776,17 @@ describe("Launch Pad Fun", () => {
     // prepare args
     const args = {
       amount: 9_000_000_869_944_000n,
+      minAssetOut: 903_362_042n,
     } as SellTokenInstructionDataArgs;
 
+    const investorBefore = await rpcClient.rpc
+      .getBalance(investor.address, { commitment: "confirmed" })
+      .send();
+
     const ix = await program.getSellTokenInstructionAsync({
-      investor: creator,
+      investor: investor,
       mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
       ...args,
     });
 
@@ -631,10 +847,186 @@ describe("Launch Pad Fun", () => {
     expect(investorTokenAccount).to.not.be.null;
     expect(investorTokenAccount.value.amount).to.equal("851972000000057"); //851.972,000000057
 
-    const investor = await rpcClient.rpc.getAccountInfo(creator.address).send();
+    const investorAfter = await rpcClient.rpc
+      .getBalance(investor.address, { commitment: "confirmed" })
+      .send();
+
+    expect(investorAfter.value - investorBefore.value).to.equal(903_362_042n);
+  });
+
+  it("rejects trades below the minimum output", async () => {
+    const { programClient: program, investor, mint } = testEnv;
+
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000_000n,
+      minTokenOut: 1_000_000_000_000_000_000n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [buyTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__SLIPPAGE_EXCEEDED
+    );
+
+    const sellTokenIx = await program.getSellTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000n,
+      minAssetOut: 1_000_000_000n,
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [sellTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__SLIPPAGE_EXCEEDED
+    );
+  });
+
+  it("rejects creator sells before the creator sell delay", async () => {
+    const { programClient: program, creator, mint } = testEnv;
+
+    const ix = await program.getSellTokenInstructionAsync({
+      investor: creator,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000n,
+      minAssetOut: 0n,
+    });
+
+    await expectLaunchPadError(
+      testEnv,
+      [ix],
+      program.LAUNCHPAD_FUN_ERROR__CREATOR_SELL_DELAY_NOT_MET
+    );
+  });
+
+  it("pauses the protocol and blocks trading", async () => {
+    const { rpcClient, programClient: program, authority, creator, mint } =
+      testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    const [launchPadConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("launch_pad_config:")],
+      programId
+    );
+
+    // only the config authority can pause
+    await expectLaunchPadError(
+      testEnv,
+      [await program.getPauseProtocolInstructionAsync({ authority: creator })],
+      program.LAUNCHPAD_FUN_ERROR__INVALID_AUTHORITY
+    );
+
+    const pauseIx = await program.getPauseProtocolInstructionAsync({
+      authority: authority,
+    });
+    const txSignature = await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([pauseIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    console.log("tx", txSignature.toString());
+
+    let cfg = await testEnv.programClient.fetchLaunchPadConfig(
+      rpcClient.rpc,
+      launchPadConfigPda.toString() as Address,
+      { commitment: "confirmed" }
+    );
+    expect(cfg.data.status).to.equal(2); // ProtocolStatus::Paused (enum idx)
+
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: creator,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [buyTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__PROTOCOL_CONFIG_NOT_ACTIVE
+    );
+
+    const sellTokenIx = await program.getSellTokenInstructionAsync({
+      investor: creator,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000n,
+      minAssetOut: 0n,
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [sellTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__PROTOCOL_CONFIG_NOT_ACTIVE
+    );
+
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: await generateKeyPairSigner(),
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Paused Meme Token",
+      symbol: "PMT",
+      uri: "https://example.com/pmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [createTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__PROTOCOL_CONFIG_NOT_ACTIVE
+    );
+
+    const { graduateIx } = await getGraduateToRaydiumInstruction(testEnv);
+    await expectLaunchPadError(
+      testEnv,
+      [getSetComputeUnitLimitInstruction({ units: 600_000 }), graduateIx],
+      program.LAUNCHPAD_FUN_ERROR__PROTOCOL_CONFIG_NOT_ACTIVE
+    );
+  });
+
+  it("unpauses the protocol", async () => {
+    const { rpcClient, programClient: program, authority } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    const [launchPadConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("launch_pad_config:")],
+      programId
+    );
+
+    const unpauseIx = await program.getUnpauseProtocolInstructionAsync({
+      authority: authority,
+    });
+    const txSignature = await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([unpauseIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    console.log("tx", txSignature.toString());
 
-    expect(investor).to.not.be.null;
-    expect(BigInt(investor.value.lamports.toString())).to.equal(9_893_151_722n);
+    let cfg = await testEnv.programClient.fetchLaunchPadConfig(
+      rpcClient.rpc,
+      launchPadConfigPda.toString() as Address,
+      { commitment: "confirmed" }
+    );
+    expect(cfg.data.status).to.equal(1); // ProtocolStatus::Active (enum idx)
   });
 
   it("buys a token and graduate", async () => {
@@ -697,85 +1089,21 @@ describe("Launch Pad Fun", () => {
     //   "D4FPEruKEHrG5TenZ2mpDGEfu1iUvTiqBxvpU8HLBvC2"
     // );
 
-    const raydiumCpmmProgramId = new anchor.web3.PublicKey(
-      "DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb"
-    );
-    const ammConfigPda = new anchor.web3.PublicKey(
-      "A9qBhPy4k5UYW72hSgAkh1Epr2do69P54yzzcMV3yv6b"
-    );
-
-    const tokenArray = [NATIVE_MINT, new anchor.web3.PublicKey(mint.address)];
-    tokenArray.sort((a, b) => {
-      const bufferA = a.toBuffer();
-      const bufferB = b.toBuffer();
-      return Buffer.compare(bufferA, bufferB);
-    });
-    const token0Mint = tokenArray[0];
-    const token1Mint = tokenArray[1];
-
-    const [poolStatePda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("pool"),
-        ammConfigPda.toBuffer(),
-        token0Mint.toBuffer(),
-        token1Mint.toBuffer(),
-      ],
-      raydiumCpmmProgramId
-    );
-
-    const [token0VaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("pool_vault"),
-        poolStatePda.toBuffer(),
-        token0Mint.toBuffer(),
-      ],
-      raydiumCpmmProgramId
-    );
-
-    const [token1VaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("pool_vault"),
-        poolStatePda.toBuffer(),
-        token1Mint.toBuffer(),
-      ],
-      raydiumCpmmProgramId
-    );
-    const [lpMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("pool_lp_mint"), poolStatePda.toBuffer()],
-      raydiumCpmmProgramId
-    );
-
-    const creatorAddressBytes = codec.encode(creator.address.toString());
-    const [lpTokenPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(creatorAddressBytes), // owner
-        TOKEN_PROGRAM_ID.toBuffer(),
-        lpMintPda.toBuffer(),
-      ],
-      ASSOCIATED_TOKEN_PROGRAM_ID
-    );
-
-    const graduateIx = await program.getGraduateToRaydiumInstructionAsync({
-      investor: creator,
-      ammConfig: ammConfigPda.toBase58() as Address,
-      poolState: poolStatePda.toBase58() as Address,
-      lpToken: lpTokenPda.toBase58() as Address,
-      token0Vault: token0VaultPda.toBase58() as Address,
-      token1Vault: token1VaultPda.toBase58() as Address,
-      mint: mint.address,
-      wsolMint: NATIVE_MINT.toBase58() as Address,
-      // wsolMint: mint.address,
-      // mint: NATIVE_MINT.toBase58() as Address,
-    });
+    const { graduateIx, token0VaultPda, token1VaultPda, lpTokenPda } =
+      await getGraduateToRaydiumInstruction(testEnv);
 
     // prepare args
     const args = {
       amount: 110_000_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
     } as BuyTokenInstructionDataArgs;
 
     const buyTokenIx = await program.getBuyTokenInstructionAsync({
       investor: creator,
       mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
       ...args,
     });
 
@@ -838,13 +1166,1760 @@ describe("Launch Pad Fun", () => {
       .send();
 
     expect(token1VaultPdaAccount?.value).to.not.be.null;
-    expect(token1VaultPdaAccount.value.amount).to.equal("477246623315581827");
+    // exactly the graduation allocation goes to the pool
+    expect(token1VaultPdaAccount.value.amount).to.equal("200000000000000000");
+
+    // the 277246623315581827 unsold curve tokens above the allocation are burned
+    const supply = await rpcClient.rpc
+      .getTokenSupply(mint.address, { commitment: "confirmed" })
+      .send();
+    expect(supply.value.amount).to.equal("722753376684418173");
 
     const lpTokenPdaAccount = await rpcClient.rpc
       .getTokenAccountBalance(lpTokenPda.toString() as Address)
       .send();
 
     expect(lpTokenPdaAccount?.value).to.not.be.null;
-    expect(lpTokenPdaAccount.value.amount).to.equal("228638935524699");
+    expect(lpTokenPdaAccount.value.amount).to.equal("148010919007248");
+  });
+
+  it("withdraws protocol fees to the treasury", async () => {
+    const { rpcClient, programClient: program, authority } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault:")],
+      programId
+    );
+
+    const treasuryBefore = await rpcClient.rpc
+      .getBalance(authority.address, { commitment: "confirmed" })
+      .send();
+
+    // the treasury defaults to the authority that initialized the config
+    const ix = await program.getWithdrawProtocolFeesInstructionAsync({
+      authority: authority,
+      treasury: authority.address,
+      amount: null,
+    });
+
+    const txSignature = await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([ix], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    console.log("tx", txSignature.toString());
+
+    const vault = await rpcClient.rpc
+      .getAccountInfo(vaultPda.toString() as Address)
+      .send();
+
+    // only the rent exempt minimum is left behind
+    expect(BigInt(vault.value.lamports.toString())).to.equal(890880n);
+
+    const treasuryAfter = await rpcClient.rpc
+      .getBalance(authority.address, { commitment: "confirmed" })
+      .send();
+
+    // 564_333_071 collected fees - 890_880 rent - 5_000 tx fee
+    expect(treasuryAfter.value - treasuryBefore.value).to.equal(563_437_191n);
+
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getWithdrawProtocolFeesInstructionAsync({
+          authority: authority,
+          treasury: authority.address,
+          amount: 1n,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__INSUFFICIENT_PROTOCOL_FEES
+    );
+  });
+
+  it("partially fills a buy that crosses the graduation cap", async () => {
+    const { rpcClient, programClient: program, creator, investor } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    await airdropFactory(rpcClient)({
+      recipientAddress: investor.address,
+      lamports: lamports(500_000_000_000n),
+      commitment: "confirmed",
+    });
+
+    const mint = await generateKeyPairSigner();
+    const codec = getBase58Encoder();
+    const mintAddressBytes = codec.encode(mint.address.toString());
+
+    const [launchPadTokenPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("launch_pad_token:"), Buffer.from(mintAddressBytes)],
+      programId
+    );
+
+    const [investorTokenAccountPda] = await findAssociatedTokenPda({
+      owner: investor.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      mint: mint.address,
+    });
+
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Capped Meme Token",
+      symbol: "CMT",
+      uri: "https://example.com/cmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    // 450 SOL buys more than the 800M tokens left before graduation
+    const args = {
+      amount: 450_000_000_000n,
+      minTokenOut: 0n,
+    };
+
+    const strictBuyIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      ...args,
+      strictFill: true,
+      allowlistProof: null,
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [strictBuyIx],
+      program.LAUNCHPAD_FUN_ERROR__INSUFFICIENT_TOKEN_LIQUIDITY
+    );
+
+    const investorBefore = await rpcClient.rpc
+      .getBalance(investor.address, { commitment: "confirmed" })
+      .send();
+
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      ...args,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    const txSignature = await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([buyTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    console.log("tx", txSignature.toString());
+
+    let token = await testEnv.programClient.fetchLaunchPadToken(
+      testEnv.rpcClient.rpc,
+      launchPadTokenPda.toString() as Address,
+      { commitment: "confirmed" }
+    );
+
+    expect(token.data.virtualTokenAmount).to.equal(200000000000000000n);
+    expect(token.data.virtualAssetAmount).to.equal(500000000001n);
+    expect(token.data.virtualGraduationAmount).to.equal(400000000001n);
+    expect(token.data.status).to.equal(2); // LaunchPadTokenStatus::ReadyToGraduate (enum idx)
+
+    const investorTokenAccount = await rpcClient.rpc
+      .getTokenAccountBalance(investorTokenAccountPda.toString() as Address)
+      .send();
+
+    expect(investorTokenAccount.value.amount).to.equal("800000000000000000");
+
+    const investorAfter = await rpcClient.rpc
+      .getBalance(investor.address, { commitment: "confirmed" })
+      .send();
+
+    // only the 400 SOL fill plus its 0.5% fee and the token account rent are charged
+    const spent = investorBefore.value - investorAfter.value;
+    expect(spent < 405_000_000_000n).to.equal(true);
+    expect(spent > 400_000_000_000n).to.equal(true);
+  });
+
+  it("rejects curve types the config does not allow", async () => {
+    const { programClient: program, creator } = testEnv;
+
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: await generateKeyPairSigner(),
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Linear Meme Token",
+      symbol: "LMT",
+      uri: "https://example.com/lmt.json",
+      curveType: programClient.curveType("Linear", {
+        initialPrice: 28_000_000_000n,
+        slope: 100_000_000_000n,
+      }),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [createTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__CURVE_TYPE_NOT_ALLOWED
+    );
+  });
+
+  it("creates a token with a custom supply, decimals and graduation allocation", async () => {
+    const { rpcClient, programClient: program, creator } = testEnv;
+    const mint = await generateKeyPairSigner();
+
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Partner Meme Token",
+      symbol: "PART",
+      uri: "https://example.com/part.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 100_000_000n,
+      decimals: 6,
+      graduationAllocation: 300_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      findLaunchPadTokenPda({ ...testEnv, mint }),
+      { commitment: "confirmed" }
+    );
+    expect(token.data.tokenSupply).to.equal(100_000_000_000_000n);
+    expect(token.data.decimals).to.equal(6);
+    expect(token.data.graduationTokenAmount).to.equal(30_000_000_000_000n);
+    expect(token.data.virtualTokenAmount).to.equal(100_000_000_000_000n);
+
+    const supply = await rpcClient.rpc
+      .getTokenSupply(mint.address, { commitment: "confirmed" })
+      .send();
+    expect(supply.value.amount).to.equal("100000000000000");
+    expect(supply.value.decimals).to.equal(6);
+
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getCreateTokenInstructionAsync({
+          creator: creator,
+          mint: await generateKeyPairSigner(),
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+          name: "Oversized Meme Token",
+          symbol: "OMT",
+          uri: "https://example.com/omt.json",
+          curveType: programClient.curveType("ConstantProduct"),
+          tokenSupply: 100_000_000_000n,
+          decimals: 9,
+          graduationAllocation: 200_000,
+          initialBuyLamports: null,
+          tradingStartsAt: null,
+          allowlist: null,
+          auctionSchedule: null,
+          vestingSchedule: null,
+          additionalMetadata: [],
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__INVALID_TOKEN_SUPPLY
+    );
+  });
+
+  it("launches and buys a token priced in an allowlisted SPL quote mint", async () => {
+    const { rpcClient, programClient: program, authority, creator, investor } =
+      testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    const payer = anchor.web3.Keypair.generate();
+    await rpcClient.connection.confirmTransaction(
+      await rpcClient.connection.requestAirdrop(payer.publicKey, 1_000_000_000),
+      "confirmed"
+    );
+    const quoteMint = await createMint(
+      rpcClient.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6
+    );
+    const investorQuoteAccount = await getOrCreateAssociatedTokenAccount(
+      rpcClient.connection,
+      payer,
+      quoteMint,
+      new anchor.web3.PublicKey(investor.address)
+    );
+    await mintTo(
+      rpcClient.connection,
+      payer,
+      quoteMint,
+      investorQuoteAccount.address,
+      payer,
+      1_000_000_000
+    );
+
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      quoteMint: quoteMint.toBase58() as Address,
+      name: "Stable Meme Token",
+      symbol: "SMT",
+      uri: "https://example.com/smt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await expectLaunchPadError(
+      testEnv,
+      [createTokenIx],
+      program.LAUNCHPAD_FUN_ERROR__QUOTE_MINT_NOT_ALLOWED
+    );
+
+    const addQuoteMintIx = await program.getAddQuoteMintInstructionAsync({
+      authority: authority,
+      quoteMint: quoteMint.toBase58() as Address,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) =>
+        appendTransactionMessageInstructions([addQuoteMintIx, createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      findLaunchPadTokenPda({ ...testEnv, mint }),
+      { commitment: "confirmed" }
+    );
+    expect(token.data.quoteMint).to.equal(quoteMint.toBase58());
+
+    const mintAddressBytes = getBase58Encoder().encode(mint.address.toString());
+    const [vaultGraduationPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_graduation:"), Buffer.from(mintAddressBytes)],
+      programId
+    );
+    const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault:")],
+      programId
+    );
+    const vaultQuoteAccount = getAssociatedTokenAddressSync(
+      quoteMint,
+      vaultGraduationPda,
+      true
+    );
+    const feeQuoteAccount = getAssociatedTokenAddressSync(
+      quoteMint,
+      vaultPda,
+      true
+    );
+    const quoteAccounts = {
+      quoteMint: quoteMint.toBase58() as Address,
+      investorQuoteAccount: investorQuoteAccount.address.toBase58() as Address,
+      vaultQuoteAccount: vaultQuoteAccount.toBase58() as Address,
+      feeQuoteAccount: feeQuoteAccount.toBase58() as Address,
+      quoteTokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+    };
+
+    // the quote accounts are required for launches not priced in SOL
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getBuyTokenInstructionAsync({
+          investor: investor,
+          mint: mint.address,
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+          amount: 100_000_000n,
+          minTokenOut: 0n,
+          strictFill: false,
+          allowlistProof: null,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__MISSING_QUOTE_ACCOUNTS
+    );
+
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      ...quoteAccounts,
+      amount: 100_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([buyTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const investorQuoteBalance = await rpcClient.rpc
+      .getTokenAccountBalance(
+        investorQuoteAccount.address.toBase58() as Address,
+        { commitment: "confirmed" }
+      )
+      .send();
+    expect(investorQuoteBalance.value.amount).to.equal("900000000");
+
+    const vaultQuoteBalance = await rpcClient.rpc
+      .getTokenAccountBalance(vaultQuoteAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    const feeQuoteBalance = await rpcClient.rpc
+      .getTokenAccountBalance(feeQuoteAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    expect(
+      BigInt(vaultQuoteBalance.value.amount) +
+        BigInt(feeQuoteBalance.value.amount)
+    ).to.equal(100_000_000n);
+    expect(BigInt(feeQuoteBalance.value.amount) > 0n).to.equal(true);
+
+    // the fees collected in the quote mint are withdrawn to the treasury's token account
+    const withdrawIx = await program.getWithdrawProtocolQuoteFeesInstructionAsync(
+      {
+        authority: authority,
+        treasury: authority.address,
+        quoteMint: quoteMint.toBase58() as Address,
+        vaultQuoteAccount: feeQuoteAccount.toBase58() as Address,
+        treasuryQuoteAccount: getAssociatedTokenAddressSync(
+          quoteMint,
+          new anchor.web3.PublicKey(authority.address)
+        ).toBase58() as Address,
+        tokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+        amount: null,
+      }
+    );
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([withdrawIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const feeQuoteBalanceAfter = await rpcClient.rpc
+      .getTokenAccountBalance(feeQuoteAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    expect(feeQuoteBalanceAfter.value.amount).to.equal("0");
+  });
+
+  it("accrues the creator share of trade fees and lets the creator claim it", async () => {
+    const { rpcClient, programClient: program, authority, creator, investor } =
+      testEnv;
+
+    const updateConfigIx = await program.getUpdateLaunchPadConfigInstructionAsync(
+      {
+        authority: authority,
+        assetRate: null,
+        creatorSellDelay: null,
+        graduateThreshold: null,
+        protocolBuyFee: null,
+        protocolSellFee: null,
+        treasury: null,
+        allowedCurves: null,
+        supplyBounds: null,
+        creatorFeeBps: 200_000,
+        referralFeeBps: null,
+        launchFee: null,
+        antiSnipe: null,
+        maxVestingShare: null,
+        metadataRules: null,
+      }
+    );
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Creator Meme Token",
+      symbol: "CRMT",
+      uri: "https://example.com/crmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) =>
+        appendTransactionMessageInstructions(
+          [updateConfigIx, createTokenIx, buyTokenIx],
+          tx
+        ),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const launchPadTokenPda = findLaunchPadTokenPda({ ...testEnv, mint });
+    let token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      launchPadTokenPda,
+      { commitment: "confirmed" }
+    );
+    // 20% of the 5_000_000 lamports buy fee
+    expect(token.data.creatorFees).to.equal(1_000_000n);
+
+    const creatorBefore = await rpcClient.rpc
+      .getBalance(creator.address, { commitment: "confirmed" })
+      .send();
+
+    const claimIx = await program.getClaimCreatorFeesInstructionAsync({
+      creator: creator,
+      mint: mint.address,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([claimIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const creatorAfter = await rpcClient.rpc
+      .getBalance(creator.address, { commitment: "confirmed" })
+      .send();
+    expect(creatorAfter.value - creatorBefore.value).to.equal(1_000_000n);
+
+    token = await program.fetchLaunchPadToken(rpcClient.rpc, launchPadTokenPda, {
+      commitment: "confirmed",
+    });
+    expect(token.data.creatorFees).to.equal(0n);
+
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getClaimCreatorFeesInstructionAsync({
+          creator: creator,
+          mint: mint.address,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__NO_CREATOR_FEES_TO_CLAIM
+    );
+  });
+
+  it("pays referrers a share of the protocol fee and tracks referred volume", async () => {
+    const { rpcClient, programClient: program, authority, creator, investor } =
+      testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+    const referrer = await generateKeyPairSignerWithSol(rpcClient);
+
+    const [referralPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("referral:"),
+        new anchor.web3.PublicKey(referrer.address).toBuffer(),
+        NATIVE_MINT.toBuffer(),
+      ],
+      programId
+    );
+    const referral = referralPda.toBase58() as Address;
+
+    const updateConfigIx = await program.getUpdateLaunchPadConfigInstructionAsync(
+      {
+        authority: authority,
+        assetRate: null,
+        creatorSellDelay: null,
+        graduateThreshold: null,
+        protocolBuyFee: null,
+        protocolSellFee: null,
+        treasury: null,
+        allowedCurves: null,
+        supplyBounds: null,
+        creatorFeeBps: null,
+        referralFeeBps: 100_000,
+        launchFee: null,
+        antiSnipe: null,
+        maxVestingShare: null,
+        metadataRules: null,
+      }
+    );
+    const initReferralIx = await program.getInitReferralInstructionAsync({
+      referrer: referrer,
+      quoteMint: NATIVE_MINT.toBase58() as Address,
+    });
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Referred Meme Token",
+      symbol: "RFMT",
+      uri: "https://example.com/rfmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      referral: referral,
+      amount: 1_000_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) =>
+        appendTransactionMessageInstructions(
+          [updateConfigIx, initReferralIx, createTokenIx, buyTokenIx],
+          tx
+        ),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    let referralAccount = await program.fetchReferral(rpcClient.rpc, referral, {
+      commitment: "confirmed",
+    });
+    // 10% of the 4_000_000 lamports left after the 20% creator share of the buy fee
+    expect(referralAccount.data.unclaimedFees).to.equal(400_000n);
+    expect(referralAccount.data.lifetimeVolume).to.equal(1_000_000_000n);
+    expect(referralAccount.data.tradeCount).to.equal(1n);
+
+    // referrers cannot earn fees on their own trades
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getBuyTokenInstructionAsync({
+          investor: referrer,
+          mint: mint.address,
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+          referral: referral,
+          amount: 100_000_000n,
+          minTokenOut: 0n,
+          strictFill: false,
+          allowlistProof: null,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__SELF_REFERRAL
+    );
+
+    const referrerBefore = await rpcClient.rpc
+      .getBalance(referrer.address, { commitment: "confirmed" })
+      .send();
+    const withdrawIx = await program.getWithdrawReferralFeesInstructionAsync({
+      referrer: referrer,
+      referral: referral,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([withdrawIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    const referrerAfter = await rpcClient.rpc
+      .getBalance(referrer.address, { commitment: "confirmed" })
+      .send();
+    expect(referrerAfter.value - referrerBefore.value).to.equal(400_000n);
+
+    referralAccount = await program.fetchReferral(rpcClient.rpc, referral, {
+      commitment: "confirmed",
+    });
+    expect(referralAccount.data.unclaimedFees).to.equal(0n);
+    expect(referralAccount.data.lifetimeFees).to.equal(400_000n);
+  });
+
+  it("caps buys per wallet during the anti-snipe window", async () => {
+    const { programClient: program, authority, creator, investor } = testEnv;
+
+    const updateAntiSnipe = (antiSnipe: programClient.AntiSnipeLimitArgs) =>
+      program.getUpdateLaunchPadConfigInstructionAsync({
+        authority: authority,
+        assetRate: null,
+        creatorSellDelay: null,
+        graduateThreshold: null,
+        protocolBuyFee: null,
+        protocolSellFee: null,
+        treasury: null,
+        allowedCurves: null,
+        supplyBounds: null,
+        creatorFeeBps: null,
+        referralFeeBps: null,
+        launchFee: null,
+        antiSnipe,
+        maxVestingShare: null,
+        metadataRules: null,
+      });
+
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Guarded Meme Token",
+      symbol: "GMT",
+      uri: "https://example.com/gmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    // no wallet may buy more than 1% of the supply in the first hour
+    const enableAntiSnipeIx = await updateAntiSnipe({
+      maxWalletShare: 10_000,
+      window: 3_600n,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) =>
+        appendTransactionMessageInstructions(
+          [enableAntiSnipeIx, createTokenIx],
+          tx
+        ),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const buy = (amount: bigint) =>
+      program.getBuyTokenInstructionAsync({
+        investor: investor,
+        mint: mint.address,
+        tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+        amount,
+        minTokenOut: 0n,
+        strictFill: false,
+        allowlistProof: null,
+      });
+
+    // 0.5 SOL buys about 0.5% of the supply
+    const buyTokenIx = await buy(500_000_000n);
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([buyTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const mintAddressBytes = getBase58Encoder().encode(mint.address.toString());
+    const [buyerRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("buyer_record:"),
+        Buffer.from(mintAddressBytes),
+        new anchor.web3.PublicKey(investor.address).toBuffer(),
+      ],
+      new anchor.web3.PublicKey(program.LAUNCHPAD_FUN_PROGRAM_ADDRESS)
+    );
+    const buyerRecord = await program.fetchBuyerRecord(
+      testEnv.rpcClient.rpc,
+      buyerRecordPda.toBase58() as Address,
+      { commitment: "confirmed" }
+    );
+    expect(buyerRecord.data.boughtAmount > 0n).to.equal(true);
+
+    // another 0.6 SOL would take the wallet past 1%
+    await expectLaunchPadError(
+      testEnv,
+      [await buy(600_000_000n)],
+      program.LAUNCHPAD_FUN_ERROR__MAX_WALLET_BUY_EXCEEDED
+    );
+
+    const disableAntiSnipeIx = await updateAntiSnipe({
+      maxWalletShare: 0,
+      window: 0n,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([disableAntiSnipeIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+  });
+
+  it("bundles a creator dev-buy into token creation", async () => {
+    const { rpcClient, programClient: program, creator } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    const mint = await generateKeyPairSigner();
+    const mintAddressBytes = getBase58Encoder().encode(mint.address.toString());
+    const [buyerRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("buyer_record:"),
+        Buffer.from(mintAddressBytes),
+        new anchor.web3.PublicKey(creator.address).toBuffer(),
+      ],
+      programId
+    );
+    const creatorTokenAccount = getAssociatedTokenAddressSync(
+      new anchor.web3.PublicKey(mint.address),
+      new anchor.web3.PublicKey(creator.address),
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Dev Buy Meme Token",
+      symbol: "DBMT",
+      uri: "https://example.com/dbmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: 1_000_000_000n,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+      creatorTokenAccount: creatorTokenAccount.toBase58() as Address,
+      buyerRecord: buyerRecordPda.toBase58() as Address,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const creatorBalance = await rpcClient.rpc
+      .getTokenAccountBalance(creatorTokenAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    expect(BigInt(creatorBalance.value.amount) > 0n).to.equal(true);
+
+    const token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      findLaunchPadTokenPda({ ...testEnv, mint }),
+      { commitment: "confirmed" }
+    );
+    // 20% of the 5_000_000 lamports buy fee
+    expect(token.data.creatorFees).to.equal(1_000_000n);
+
+    // the dev-buy is locked by the creator sell delay like any creator position
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getSellTokenInstructionAsync({
+          investor: creator,
+          mint: mint.address,
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+          amount: 1_000_000n,
+          minAssetOut: 0n,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__CREATOR_SELL_DELAY_NOT_MET
+    );
+  });
+
+  it("opens scheduled launches for trading at the start time", async () => {
+    const { rpcClient, programClient: program, creator, investor } = testEnv;
+
+    const slot = await rpcClient.rpc.getSlot({ commitment: "confirmed" }).send();
+    const now = await rpcClient.rpc.getBlockTime(slot).send();
+    const tradingStartsAt = BigInt(now) + 5n;
+
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Scheduled Meme Token",
+      symbol: "SMT",
+      uri: "https://example.com/smt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const launchPadTokenPda = findLaunchPadTokenPda({ ...testEnv, mint });
+    let token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      launchPadTokenPda,
+      { commitment: "confirmed" }
+    );
+    expect(token.data.status).to.equal(4); // LaunchPadTokenStatus::Scheduled (enum idx)
+    expect(token.data.tradingStartsAt).to.equal(tradingStartsAt);
+
+    const buy = () =>
+      program.getBuyTokenInstructionAsync({
+        investor: investor,
+        mint: mint.address,
+        tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+        amount: 100_000_000n,
+        minTokenOut: 0n,
+        strictFill: false,
+        allowlistProof: null,
+      });
+    await expectLaunchPadError(
+      testEnv,
+      [await buy()],
+      program.LAUNCHPAD_FUN_ERROR__TRADING_NOT_STARTED
+    );
+
+    await new Promise((resolve) => setTimeout(resolve, 7_000));
+    const buyTokenIx = await buy();
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([buyTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    token = await program.fetchLaunchPadToken(rpcClient.rpc, launchPadTokenPda, {
+      commitment: "confirmed",
+    });
+    expect(token.data.status).to.equal(1); // LaunchPadTokenStatus::TradingEnabled (enum idx)
+  });
+
+  it("limits buys to allowlisted allocations during the presale", async () => {
+    const { rpcClient, programClient: program, creator, investor } = testEnv;
+
+    // mirrors allowlist_leaf and hash_pair in the program
+    const sha256 = (...parts: Buffer[]) =>
+      createHash("sha256").update(Buffer.concat(parts)).digest();
+    const leaf = (buyer: Address, allocation: bigint) => {
+      const amount = Buffer.alloc(8);
+      amount.writeBigUInt64LE(allocation);
+      return sha256(
+        Buffer.from([0]),
+        new anchor.web3.PublicKey(buyer).toBuffer(),
+        amount
+      );
+    };
+    const hashPair = (a: Buffer, b: Buffer) =>
+      Buffer.compare(a, b) <= 0
+        ? sha256(Buffer.from([1]), a, b)
+        : sha256(Buffer.from([1]), b, a);
+
+    // about 1.5x what 0.1 SOL buys on a fresh curve
+    const allocation = 1_500_000_000_000_000n;
+    const other = await generateKeyPairSigner();
+    const investorLeaf = leaf(investor.address, allocation);
+    const otherLeaf = leaf(other.address, allocation);
+    const merkleRoot = hashPair(investorLeaf, otherLeaf);
+
+    const slot = await rpcClient.rpc.getSlot({ commitment: "confirmed" }).send();
+    const now = await rpcClient.rpc.getBlockTime(slot).send();
+
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Presale Meme Token",
+      symbol: "PMT",
+      uri: "https://example.com/pmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: {
+        merkleRoot: new Uint8Array(merkleRoot),
+        endsAt: BigInt(now) + 8n,
+      },
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const buy = (
+      allowlistProof: programClient.AllowlistProofArgs | null
+    ) =>
+      program.getBuyTokenInstructionAsync({
+        investor: investor,
+        mint: mint.address,
+        tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+        amount: 100_000_000n,
+        minTokenOut: 0n,
+        strictFill: false,
+        allowlistProof,
+      });
+    const proof = { allocation, proof: [new Uint8Array(otherLeaf)] };
+
+    await expectLaunchPadError(
+      testEnv,
+      [await buy(null)],
+      program.LAUNCHPAD_FUN_ERROR__ALLOWLIST_PROOF_REQUIRED
+    );
+    await expectLaunchPadError(
+      testEnv,
+      [await buy({ ...proof, allocation: allocation + 1n })],
+      program.LAUNCHPAD_FUN_ERROR__INVALID_ALLOWLIST_PROOF
+    );
+
+    const presaleBuyIx = await buy(proof);
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([presaleBuyIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    await expectLaunchPadError(
+      testEnv,
+      [await buy(proof)],
+      program.LAUNCHPAD_FUN_ERROR__ALLOWLIST_ALLOCATION_EXCEEDED
+    );
+
+    // public trading resumes once the presale ends
+    await new Promise((resolve) => setTimeout(resolve, 10_000));
+    const publicBuyIx = await buy(null);
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([publicBuyIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+  });
+
+  it("opens an auction launch with a sealed-bid batch auction", async () => {
+    const { rpcClient, programClient: program, creator, investor } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+    const sleep = (ms: number) =>
+      new Promise((resolve) => setTimeout(resolve, ms));
+
+    // mirrors auction_bid_commitment in the program
+    const commitment = (bidder: Address, amount: bigint, salt: Buffer) => {
+      const amountBytes = Buffer.alloc(8);
+      amountBytes.writeBigUInt64LE(amount);
+      return new Uint8Array(
+        createHash("sha256")
+          .update(new anchor.web3.PublicKey(bidder).toBuffer())
+          .update(amountBytes)
+          .update(salt)
+          .digest()
+      );
+    };
+
+    const slot = await rpcClient.rpc.getSlot({ commitment: "confirmed" }).send();
+    const now = BigInt(await rpcClient.rpc.getBlockTime(slot).send());
+
+    const mint = await generateKeyPairSigner();
+    const mintAddressBytes = getBase58Encoder().encode(mint.address.toString());
+    const [auctionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("auction:"), Buffer.from(mintAddressBytes)],
+      programId
+    );
+    // the creator dev-buy would run ahead of every bid
+    const devBuyMint = await generateKeyPairSigner();
+    const [devBuyAuctionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("auction:"),
+        Buffer.from(getBase58Encoder().encode(devBuyMint.address.toString())),
+      ],
+      programId
+    );
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getCreateTokenInstructionAsync({
+          creator: creator,
+          mint: devBuyMint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+          name: "Auction Meme Token",
+          symbol: "AMT",
+          uri: "https://example.com/amt.json",
+          curveType: programClient.curveType("ConstantProduct"),
+          tokenSupply: 1_000_000_000n,
+          decimals: 9,
+          graduationAllocation: 200_000,
+          initialBuyLamports: 1_000_000_000n,
+          tradingStartsAt: null,
+          allowlist: null,
+          auctionSchedule: { commitEndsAt: now + 6n, revealEndsAt: now + 12n },
+          vestingSchedule: null,
+          additionalMetadata: [],
+          auction: devBuyAuctionPda.toBase58() as Address,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__INITIAL_BUY_NOT_ALLOWED
+    );
+
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Auction Meme Token",
+      symbol: "AMT",
+      uri: "https://example.com/amt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: { commitEndsAt: now + 6n, revealEndsAt: now + 12n },
+      vestingSchedule: null,
+      additionalMetadata: [],
+      auction: auctionPda.toBase58() as Address,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    // trading stays closed until the auction settles
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getBuyTokenInstructionAsync({
+          investor: investor,
+          mint: mint.address,
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+          amount: 100_000_000n,
+          minTokenOut: 0n,
+          strictFill: false,
+          allowlistProof: null,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__LAUNCH_PAD_TOKEN_TRADING_NOT_ENABLED
+    );
+
+    // the second bidder bids half as much, deposits hide the real bids
+    const other = await generateKeyPairSignerWithSol(rpcClient, 2_000_000_000n);
+    const bids = [
+      { bidder: investor, amount: 1_000_000_000n, deposit: 1_500_000_000n },
+      { bidder: other, amount: 500_000_000n, deposit: 1_000_000_000n },
+    ].map((bid) => ({ ...bid, salt: randomBytes(32) }));
+
+    const commitIxs = await Promise.all(
+      bids.map((bid) =>
+        program.getCommitAuctionBidInstructionAsync({
+          bidder: bid.bidder,
+          mint: mint.address,
+          commitment: commitment(bid.bidder.address, bid.amount, bid.salt),
+          deposit: bid.deposit,
+        })
+      )
+    );
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions(commitIxs, tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    await sleep(7_000);
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getRevealAuctionBidInstructionAsync({
+          bidder: investor,
+          mint: mint.address,
+          amount: bids[0].amount + 1n,
+          salt: new Uint8Array(bids[0].salt),
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__INVALID_AUCTION_REVEAL
+    );
+    const revealIxs = await Promise.all(
+      bids.map((bid) =>
+        program.getRevealAuctionBidInstructionAsync({
+          bidder: bid.bidder,
+          mint: mint.address,
+          amount: bid.amount,
+          salt: new Uint8Array(bid.salt),
+        })
+      )
+    );
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions(revealIxs, tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    await sleep(6_000);
+    const settleIx = await program.getSettleAuctionInstructionAsync({
+      payer: testEnv.authority,
+      mint: mint.address,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([settleIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      findLaunchPadTokenPda({ ...testEnv, mint }),
+      { commitment: "confirmed" }
+    );
+    expect(token.data.status).to.equal(1); // LaunchPadTokenStatus::TradingEnabled (enum idx)
+    const auction = await program.fetchAuction(
+      rpcClient.rpc,
+      auctionPda.toBase58() as Address,
+      { commitment: "confirmed" }
+    );
+    expect(auction.data.assetFilled).to.equal(1_500_000_000n);
+
+    const claimed: bigint[] = [];
+    for (const bid of bids) {
+      const balanceBefore = await rpcClient.rpc
+        .getBalance(bid.bidder.address, { commitment: "confirmed" })
+        .send();
+      const claimIx = await program.getClaimAuctionBidInstructionAsync({
+        bidder: bid.bidder,
+        mint: mint.address,
+        tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      });
+      await pipe(
+        await createDefaultTransaction(testEnv),
+        (tx) => appendTransactionMessageInstructions([claimIx], tx),
+        (tx) =>
+          signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+      );
+      const balanceAfter = await rpcClient.rpc
+        .getBalance(bid.bidder.address, { commitment: "confirmed" })
+        .send();
+      // the unspent deposit comes back, on top of the bid account rent
+      expect(balanceAfter.value - balanceBefore.value >= bid.deposit - bid.amount)
+        .to.be.true;
+
+      const bidderTokenAccount = getAssociatedTokenAddressSync(
+        new anchor.web3.PublicKey(mint.address),
+        new anchor.web3.PublicKey(bid.bidder.address),
+        false,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const tokenBalance = await rpcClient.rpc
+        .getTokenAccountBalance(bidderTokenAccount.toBase58() as Address, {
+          commitment: "confirmed",
+        })
+        .send();
+      claimed.push(BigInt(tokenBalance.value.amount));
+    }
+    // both bids paid the same clearing price
+    expect(claimed[0] / 2n - claimed[1] <= 1n).to.be.true;
+  });
+
+  it("mints a vested team allocation that unlocks after graduation", async () => {
+    const { rpcClient, programClient: program, authority, creator } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+
+    const updateConfigIx = await program.getUpdateLaunchPadConfigInstructionAsync(
+      {
+        authority: authority,
+        assetRate: null,
+        creatorSellDelay: null,
+        graduateThreshold: null,
+        protocolBuyFee: null,
+        protocolSellFee: null,
+        treasury: null,
+        allowedCurves: null,
+        supplyBounds: null,
+        creatorFeeBps: null,
+        referralFeeBps: null,
+        launchFee: null,
+        antiSnipe: null,
+        maxVestingShare: 100_000,
+        metadataRules: null,
+      }
+    );
+
+    const mint = await generateKeyPairSigner();
+    const mintAddressBytes = getBase58Encoder().encode(mint.address.toString());
+    const [vestingPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting:"), Buffer.from(mintAddressBytes)],
+      programId
+    );
+    const vestingTokenAccount = getAssociatedTokenAddressSync(
+      new anchor.web3.PublicKey(mint.address),
+      vestingPda,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Team Meme Token",
+      symbol: "TMT",
+      uri: "https://example.com/tmt.json",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: { share: 50_000, cliff: 0n, duration: 86_400n },
+      additionalMetadata: [],
+      vesting: vestingPda.toBase58() as Address,
+      vestingTokenAccount: vestingTokenAccount.toBase58() as Address,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) =>
+        appendTransactionMessageInstructions([updateConfigIx, createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    // 5% of the curve supply is minted to the vesting PDA
+    const vestingBalance = await rpcClient.rpc
+      .getTokenAccountBalance(vestingTokenAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    expect(vestingBalance.value.amount).to.equal("50000000000000000");
+
+    await expectLaunchPadError(
+      testEnv,
+      [
+        await program.getClaimVestedInstructionAsync({
+          creator: creator,
+          mint: mint.address,
+          tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+        }),
+      ],
+      program.LAUNCHPAD_FUN_ERROR__VESTING_NOT_STARTED
+    );
+  });
+
+  it("lets the creator update token metadata before graduation", async () => {
+    const { rpcClient, programClient: program, creator, investor } = testEnv;
+
+    const mint = await generateKeyPairSigner();
+    const createTokenIx = await program.getCreateTokenInstructionAsync({
+      creator: creator,
+      mint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+      name: "Typo Meme Token",
+      symbol: "TYPO",
+      uri: "https://example.com/typo.jsn",
+      curveType: programClient.curveType("ConstantProduct"),
+      tokenSupply: 1_000_000_000n,
+      decimals: 9,
+      graduationAllocation: 200_000,
+      initialBuyLamports: null,
+      tradingStartsAt: null,
+      allowlist: null,
+      auctionSchedule: null,
+      vestingSchedule: null,
+      additionalMetadata: [],
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const updateMetadata = (
+      signer: TransactionSigner,
+      uri: string | null
+    ) =>
+      program.getUpdateTokenMetadataInstructionAsync({
+        creator: signer,
+        mint: mint.address,
+        tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+        name: null,
+        symbol: null,
+        uri,
+      });
+
+    await expectLaunchPadError(
+      testEnv,
+      [await updateMetadata(investor, "https://example.com/typo.json")],
+      program.LAUNCHPAD_FUN_ERROR__INVALID_CREATOR
+    );
+    await expectLaunchPadError(
+      testEnv,
+      [await updateMetadata(creator, "x")],
+      program.LAUNCHPAD_FUN_ERROR__INVALID_TOKEN_URI_LENGTH
+    );
+    await expectLaunchPadError(
+      testEnv,
+      [await updateMetadata(creator, null)],
+      program.LAUNCHPAD_FUN_ERROR__EMPTY_TOKEN_METADATA_UPDATE
+    );
+
+    const updateMetadataIx = await updateMetadata(
+      creator,
+      "https://example.com/typo.json"
+    );
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([updateMetadataIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const metadata = await getTokenMetadata(
+      rpcClient.connection,
+      new anchor.web3.PublicKey(mint.address),
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(metadata?.uri).to.equal("https://example.com/typo.json");
+    expect(metadata?.name).to.equal("Typo Meme Token");
+  });
+
+  it("writes allowed additional metadata at creation", async () => {
+    const { rpcClient, programClient: program, authority, creator } = testEnv;
+
+    const updateConfigIx = await program.getUpdateLaunchPadConfigInstructionAsync(
+      {
+        authority: authority,
+        assetRate: null,
+        creatorSellDelay: null,
+        graduateThreshold: null,
+        protocolBuyFee: null,
+        protocolSellFee: null,
+        treasury: null,
+        allowedCurves: null,
+        supplyBounds: null,
+        creatorFeeBps: null,
+        referralFeeBps: null,
+        launchFee: null,
+        antiSnipe: null,
+        maxVestingShare: null,
+        metadataRules: {
+          allowedKeys: ["website", "twitter", "description"],
+          maxValueLength: 128,
+        },
+      }
+    );
+
+    const mint = await generateKeyPairSigner();
+    const createToken = (additionalMetadata: { key: string; value: string }[]) =>
+      program.getCreateTokenInstructionAsync({
+        creator: creator,
+        mint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID.toBase58() as Address,
+        name: "Social Meme Token",
+        symbol: "SMT",
+        uri: "https://example.com/smt.json",
+        curveType: programClient.curveType("ConstantProduct"),
+        tokenSupply: 1_000_000_000n,
+        decimals: 9,
+        graduationAllocation: 200_000,
+        initialBuyLamports: null,
+        tradingStartsAt: null,
+        allowlist: null,
+        auctionSchedule: null,
+        vestingSchedule: null,
+        additionalMetadata,
+      });
+
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([updateConfigIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    await expectLaunchPadError(
+      testEnv,
+      [await createToken([{ key: "telegram", value: "t.me/smt" }])],
+      program.LAUNCHPAD_FUN_ERROR__METADATA_KEY_NOT_ALLOWED
+    );
+
+    const createTokenIx = await createToken([
+      { key: "website", value: "https://smt.example.com" },
+      { key: "twitter", value: "@smt" },
+    ]);
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const metadata = await getTokenMetadata(
+      rpcClient.connection,
+      new anchor.web3.PublicKey(mint.address),
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(metadata?.additionalMetadata).to.deep.equal([
+      ["website", "https://smt.example.com"],
+      ["twitter", "@smt"],
+    ]);
+  });
+
+  it("launches a classic SPL Token mint with Metaplex metadata", async () => {
+    const { rpcClient, programClient: program, creator, investor } = testEnv;
+    const programId = new anchor.web3.PublicKey(
+      program.LAUNCHPAD_FUN_PROGRAM_ADDRESS
+    );
+    const metadataProgramId = new anchor.web3.PublicKey(
+      "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+    );
+
+    await airdropFactory(rpcClient)({
+      recipientAddress: creator.address,
+      lamports: lamports(110_000_000_000n),
+      commitment: "confirmed",
+    });
+
+    const mint = await generateKeyPairSigner();
+    const mintPublicKey = new anchor.web3.PublicKey(mint.address);
+    const [metadataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        metadataProgramId.toBuffer(),
+        mintPublicKey.toBuffer(),
+      ],
+      metadataProgramId
+    );
+    const createToken = (additionalMetadata: { key: string; value: string }[]) =>
+      program.getCreateTokenInstructionAsync({
+        creator: creator,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+        metadata: metadataPda.toBase58() as Address,
+        metadataProgram: metadataProgramId.toBase58() as Address,
+        name: "Classic Meme Token",
+        symbol: "CMT",
+        uri: "https://example.com/cmt.json",
+        curveType: programClient.curveType("ConstantProduct"),
+        tokenSupply: 1_000_000_000n,
+        decimals: 9,
+        graduationAllocation: 200_000,
+        initialBuyLamports: null,
+        tradingStartsAt: null,
+        allowlist: null,
+        auctionSchedule: null,
+        vestingSchedule: null,
+        additionalMetadata,
+      });
+
+    // additional metadata only exists on the Token-2022 metadata extension
+    await expectLaunchPadError(
+      testEnv,
+      [await createToken([{ key: "website", value: "https://cmt.example.com" }])],
+      program.LAUNCHPAD_FUN_ERROR__ADDITIONAL_METADATA_NOT_SUPPORTED
+    );
+
+    const createTokenIx = await createToken([]);
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([createTokenIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const mintAccount = await rpcClient.connection.getAccountInfo(
+      mintPublicKey,
+      "confirmed"
+    );
+    expect(mintAccount?.owner.toBase58()).to.equal(TOKEN_PROGRAM_ID.toBase58());
+
+    const fetchMetadata = async () => {
+      const account = await rpcClient.connection.getAccountInfo(
+        metadataPda,
+        "confirmed"
+      );
+      // key (1) + update authority (32) + mint (32), then zero padded strings
+      const values: string[] = [];
+      let offset = 65;
+      for (let i = 0; i < 3; i++) {
+        const length = account!.data.readUInt32LE(offset);
+        values.push(
+          account!.data
+            .subarray(offset + 4, offset + 4 + length)
+            .toString()
+            .replace(/\0/g, "")
+        );
+        offset += 4 + length;
+      }
+      const [name, symbol, uri] = values;
+      return { owner: account?.owner.toBase58(), name, symbol, uri };
+    };
+    const metadata = await fetchMetadata();
+    expect(metadata.owner).to.equal(metadataProgramId.toBase58());
+    expect(metadata.name).to.equal("Classic Meme Token");
+
+    // the creator updates the Metaplex metadata until graduation
+    const updateMetadataIx = await program.getUpdateTokenMetadataInstructionAsync(
+      {
+        creator: creator,
+        mint: mint.address,
+        metadata: metadataPda.toBase58() as Address,
+        metadataProgram: metadataProgramId.toBase58() as Address,
+        tokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+        name: null,
+        symbol: null,
+        uri: "https://example.com/cmt-v2.json",
+      }
+    );
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([updateMetadataIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    const updated = await fetchMetadata();
+    expect(updated.uri).to.equal("https://example.com/cmt-v2.json");
+    expect(updated.name).to.equal("Classic Meme Token");
+    expect(updated.symbol).to.equal("CMT");
+
+    // buy and sell through the classic token program
+    const investorBuyIx = await program.getBuyTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+      amount: 1_000_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([investorBuyIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const investorTokenAccount = getAssociatedTokenAddressSync(
+      mintPublicKey,
+      new anchor.web3.PublicKey(investor.address),
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const bought = await rpcClient.rpc
+      .getTokenAccountBalance(investorTokenAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    expect(BigInt(bought.value.amount) > 0n).to.equal(true);
+
+    const sellAmount = BigInt(bought.value.amount) / 2n;
+    const investorSellIx = await program.getSellTokenInstructionAsync({
+      investor: investor,
+      mint: mint.address,
+      tokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+      amount: sellAmount,
+      minAssetOut: 0n,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) => appendTransactionMessageInstructions([investorSellIx], tx),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+    const remaining = await rpcClient.rpc
+      .getTokenAccountBalance(investorTokenAccount.toBase58() as Address, {
+        commitment: "confirmed",
+      })
+      .send();
+    expect(BigInt(remaining.value.amount)).to.equal(
+      BigInt(bought.value.amount) - sellAmount
+    );
+
+    // graduate the classic mint into a Raydium pool
+    const { graduateIx } = await getGraduateToRaydiumInstruction(
+      testEnv,
+      mint.address,
+      TOKEN_PROGRAM_ID
+    );
+    const buyTokenIx = await program.getBuyTokenInstructionAsync({
+      investor: creator,
+      mint: mint.address,
+      tokenProgram: TOKEN_PROGRAM_ID.toBase58() as Address,
+      amount: 110_000_000_000n,
+      minTokenOut: 0n,
+      strictFill: false,
+      allowlistProof: null,
+    });
+    await pipe(
+      await createDefaultTransaction(testEnv),
+      (tx) =>
+        appendTransactionMessageInstructions(
+          [
+            getSetComputeUnitLimitInstruction({ units: 600_000 }),
+            buyTokenIx,
+            graduateIx,
+          ],
+          tx
+        ),
+      (tx) => signAndSendTransaction(testEnv.rpcClient, tx, "confirmed", false)
+    );
+
+    const [launchPadTokenPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("launch_pad_token:"), mintPublicKey.toBuffer()],
+      programId
+    );
+    const token = await program.fetchLaunchPadToken(
+      rpcClient.rpc,
+      launchPadTokenPda.toBase58() as Address,
+      { commitment: "confirmed" }
+    );
+    expect(token.data.status).to.equal(3); // LaunchPadTokenStatus::Graduated (enum idx)
   });
 });